@@ -0,0 +1,38 @@
+//! Prerender the example into `dist`, after `vite build --manifest`:
+//!
+//! ```sh
+//! cargo run --bin ssg -- [out_dir]
+//! ```
+
+use std::{env, fs, process::ExitCode};
+
+use rsx_web::ssg::{Manifest, SsgError, StaticSite};
+
+fn build(out_dir: &str) -> Result<(), SsgError> {
+    let manifest = match fs::read_to_string(format!("{out_dir}/.vite/manifest.json")) {
+        Ok(json) => Manifest::from_vite(&json, "index.html")?,
+        Err(_) => {
+            eprintln!("warning: no manifest in {out_dir}, the pages will not load any script");
+            Manifest::default()
+        }
+    };
+    let files = StaticSite::new(wasm::app)
+        .manifest(manifest)
+        .route("/")
+        .build(out_dir)?;
+    for file in files {
+        println!("wrote {}", file.display());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let out_dir = env::args().nth(1).unwrap_or_else(|| "dist".to_string());
+    match build(&out_dir) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
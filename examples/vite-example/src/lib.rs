@@ -3,7 +3,7 @@ use rsx::rsx;
 use rsx_web::{console_log, document, node::render_to};
 use wasm_bindgen::prelude::*;
 
-fn app() -> rsx::Element {
+pub fn app() -> rsx::Element {
     // let count = create_signal(0);
     let onclick = move |_| {
         console_log!("Hello from the WASM side !");
@@ -11,13 +11,13 @@ fn app() -> rsx::Element {
         // count.set(count.get() + 1);
     };
 
-    return rsx!(
+    rsx!(
         <main>
             <h1>This is a RSX snippet {1} </h1>
             <button onclick={onclick} name="button">Click me and look at the console</button>
             // <div>{count.get().to_string()}</div>
         </main>
-    );
+    )
 }
 
 #[wasm_bindgen(start)]
@@ -29,5 +29,5 @@ fn start() {
         .get_element_by_id("app")
         .expect("#app is undefined");
 
-    render_to(app, &root);
+    let _ = render_to(app, &root);
 }
@@ -0,0 +1,121 @@
+//! Passing values down the scope tree.
+//!
+//! A value provided with [`provide_context`] is visible to [`use_context`] in the scope it was
+//! provided in and in all the scopes created inside it, so that components do not need to pass it
+//! down explicitly. Values are looked up by type and the nearest one wins.
+
+use std::any::{Any, type_name};
+
+use crate::{
+    node::NodeId,
+    root::{Root, create_child_scope},
+};
+
+/// Provide a context value in the current scope.
+///
+/// # Panics
+/// Panics if a value of the same type was already provided in the current scope. Use
+/// [`provide_context_in_new_scope`] to shadow it instead.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{context::*, root::*};
+/// # let _ = create_root(|| {
+/// #[derive(Clone)]
+/// struct Theme(&'static str);
+///
+/// provide_context(Theme("dark"));
+/// create_child_scope(|| {
+///     assert_eq!(use_context::<Theme>().0, "dark");
+/// });
+/// # });
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn provide_context<T: 'static>(value: T) {
+    let root = Root::global();
+    provide_context_in_node(root, root.current_node.get(), value);
+}
+
+/// Create a new child scope with `value` provided in it and run `f` inside of it.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{context::*, root::*};
+/// # let _ = create_root(|| {
+/// provide_context(1);
+/// provide_context_in_new_scope(2, || assert_eq!(use_context::<i32>(), 2));
+/// assert_eq!(use_context::<i32>(), 1);
+/// # });
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn provide_context_in_new_scope<T: 'static, U>(value: T, f: impl FnOnce() -> U) -> U {
+    let mut ret = None;
+    create_child_scope(|| {
+        provide_context(value);
+        ret = Some(f());
+    });
+    ret.unwrap()
+}
+
+fn provide_context_in_node<T: 'static>(root: &Root, id: NodeId, value: T) {
+    let mut nodes = root.nodes.borrow_mut();
+    let node = nodes
+        .get_mut(id)
+        .expect("context can only be provided inside a scope");
+    if node.context.iter().any(|value| value.is::<T>()) {
+        panic!(
+            "a context of type `{}` was already provided in this scope",
+            type_name::<T>()
+        );
+    }
+    node.context.push(Box::new(value) as Box<dyn Any>);
+}
+
+/// Returns the nearest context value of type `T`, or `None` if there is none.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{context::*, root::*};
+/// # let _ = create_root(|| {
+/// assert_eq!(try_use_context::<i32>(), None);
+/// provide_context(1);
+/// assert_eq!(try_use_context::<i32>(), Some(1));
+/// # });
+/// ```
+pub fn try_use_context<T: Clone + 'static>() -> Option<T> {
+    let root = Root::global();
+    let nodes = root.nodes.borrow();
+    let mut current = nodes.get(root.current_node.get());
+    while let Some(node) = current {
+        if let Some(value) = node
+            .context
+            .iter()
+            .find_map(|value| value.downcast_ref::<T>())
+        {
+            return Some(value.clone());
+        }
+        current = nodes.get(node.parent);
+    }
+    None
+}
+
+/// Returns the nearest context value of type `T`.
+///
+/// # Panics
+/// Panics if there is no context value of type `T`.
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn use_context<T: Clone + 'static>() -> T {
+    try_use_context()
+        .unwrap_or_else(|| panic!("no context of type `{}` was provided", type_name::<T>()))
+}
+
+/// Returns the nearest context value of type `T`, or provides the value returned by `f` in the
+/// current scope if there is none.
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn use_context_or_else<T: Clone + 'static>(f: impl FnOnce() -> T) -> T {
+    try_use_context().unwrap_or_else(|| {
+        let value = f();
+        provide_context(value.clone());
+        value
+    })
+}
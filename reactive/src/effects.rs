@@ -9,7 +9,7 @@ use crate::memos::create_memo;
 ///
 /// # Example
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(0);
 ///
@@ -38,7 +38,7 @@ pub fn create_effect(f: impl FnMut() + 'static) {
 ///
 /// # Example
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(0);
 ///
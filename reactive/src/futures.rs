@@ -0,0 +1,392 @@
+//! Running futures alongside the reactive graph.
+//!
+//! Futures are driven by a small single-threaded executor: a spawned future is polled right
+//! away, and polled again as soon as its waker is called on the thread it was spawned on. A
+//! future woken from another thread, e.g. by a runtime doing IO, is only queued, since it can
+//! only be polled on its own thread: it is polled by the next call to [`run_until_stalled`] on
+//! that thread, or while [blocking](block_on) on it. This never happens in the browser since it
+//! only has one thread.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    pin::{Pin, pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread, ThreadId},
+    time::{Duration, Instant},
+};
+
+use slotmap::{SlotMap, new_key_type};
+
+use crate::{
+    context::{provide_context_in_new_scope, try_use_context},
+    node::NodeHandle,
+    root::{on_cleanup, use_current_scope},
+    signals::{ReadSignal, Signal, create_signal},
+};
+
+new_key_type! {
+    struct TaskId;
+}
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The tasks of a thread that have been woken and need to be polled. Wakers can be called from
+/// any thread, so they hold on to the queue of the thread their task was spawned on.
+struct Queue {
+    thread: ThreadId,
+    woken: Mutex<VecDeque<TaskId>>,
+    /// Woken when a task is woken from another thread, see [`run_until_stalled`].
+    notify: Mutex<Option<Waker>>,
+}
+
+thread_local! {
+    /// The spawned futures that have not completed yet. A future is taken out of its slot while
+    /// it is being polled.
+    static TASKS: RefCell<SlotMap<TaskId, Option<Task>>> = RefCell::new(SlotMap::with_key());
+    static WOKEN: Arc<Queue> = Arc::new(Queue {
+        thread: thread::current().id(),
+        woken: Mutex::default(),
+        notify: Mutex::default(),
+    });
+    /// Whether the executor is currently polling futures.
+    static POLLING: Cell<bool> = const { Cell::new(false) };
+}
+
+struct TaskWaker {
+    id: TaskId,
+    queue: Arc<Queue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.queue.woken.lock().unwrap().push_back(self.id);
+        if thread::current().id() == self.queue.thread {
+            poll_woken();
+        } else if let Some(waker) = self.queue.notify.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Poll woken tasks until none are left, unless this is already being done further up the stack.
+fn poll_woken() {
+    /// Clears `POLLING`, even if a task panics, so that the thread's tasks are polled again.
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            POLLING.with(|polling| polling.set(false));
+        }
+    }
+
+    if POLLING.with(|polling| polling.replace(true)) {
+        return;
+    }
+    let _reset = Reset;
+    let queue = WOKEN.with(Arc::clone);
+    loop {
+        let Some(id) = queue.woken.lock().unwrap().pop_front() else {
+            break;
+        };
+        let task = TASKS.with(|tasks| tasks.borrow_mut().get_mut(id).and_then(Option::take));
+        let Some(mut task) = task else {
+            // Cancelled or completed.
+            continue;
+        };
+        let queue = queue.clone();
+        let waker = Waker::from(Arc::new(TaskWaker { id, queue }));
+        let poll = task.as_mut().poll(&mut Context::from_waker(&waker));
+        TASKS.with(|tasks| {
+            let mut tasks = tasks.borrow_mut();
+            match poll {
+                Poll::Ready(()) => {
+                    tasks.remove(id);
+                }
+                // The task may have been cancelled while it was being polled.
+                Poll::Pending => {
+                    if let Some(slot) = tasks.get_mut(id) {
+                        *slot = Some(task);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Poll the tasks of the current thread that were woken from other threads. `waker` is woken
+/// the next time that happens, after which this should be called again.
+///
+/// This lets another executor, such as the one of an HTTP server, drive the futures spawned
+/// while rendering: see [`block_on`] to wait for a future on the current thread instead.
+pub fn run_until_stalled(waker: &Waker) {
+    let queue = WOKEN.with(Arc::clone);
+    *queue.notify.lock().unwrap() = Some(waker.clone());
+    poll_woken();
+}
+
+/// Unparks a thread blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Block the current thread until `future` completes, polling the spawned futures it waits for
+/// as they are woken, including from other threads.
+///
+/// This must not be called from a spawned future, nor in the browser, where the thread cannot
+/// be blocked.
+///
+/// # Example
+/// ```
+/// # use std::{future::poll_fn, sync::mpsc, task::Poll, thread};
+/// # use rsx_reactive::futures::block_on;
+/// let (tx, rx) = mpsc::channel();
+/// let value = block_on(poll_fn(|cx| match rx.try_recv() {
+///     Ok(value) => Poll::Ready(value),
+///     Err(_) => {
+///         let (tx, waker) = (tx.clone(), cx.waker().clone());
+///         thread::spawn(move || {
+///             tx.send(42).unwrap();
+///             waker.wake();
+///         });
+///         Poll::Pending
+///     }
+/// }));
+/// assert_eq!(value, 42);
+/// ```
+pub fn block_on<T>(future: impl Future<Output = T>) -> T {
+    run_blocking(future, None).unwrap()
+}
+
+/// Like [`block_on`], but gives up and returns `None` if `future` has not completed after
+/// `timeout`.
+pub fn block_on_timeout<T>(future: impl Future<Output = T>, timeout: Duration) -> Option<T> {
+    run_blocking(future, Some(Instant::now() + timeout))
+}
+
+fn run_blocking<T>(future: impl Future<Output = T>, deadline: Option<Instant>) -> Option<T> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        run_until_stalled(&waker);
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return Some(value);
+        }
+        // Parking returns at once if the thread was unparked since the last time.
+        match deadline {
+            None => thread::park(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return None;
+                }
+                thread::park_timeout(deadline - now);
+            }
+        }
+    }
+}
+
+/// Spawn a future on the current thread. It runs until completion even if the scope it was
+/// spawned in is disposed, see [`spawn_local_scoped`] otherwise.
+pub fn spawn_local(future: impl Future<Output = ()> + 'static) {
+    spawn(Box::pin(future));
+}
+
+fn spawn(task: Task) -> TaskId {
+    let id = TASKS.with(|tasks| tasks.borrow_mut().insert(Some(task)));
+    WOKEN.with(|queue| queue.woken.lock().unwrap().push_back(id));
+    poll_woken();
+    id
+}
+
+/// Spawn a future that is polled inside the current scope and cancelled when the scope is
+/// disposed.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{futures::*, root::*, signals::*};
+/// # let _ = create_root(|| {
+/// let value = create_signal(0);
+/// spawn_local_scoped(async move { value.set(1) });
+/// assert_eq!(value.get(), 1);
+/// # });
+/// ```
+pub fn spawn_local_scoped(future: impl Future<Output = ()> + 'static) {
+    let scope = use_current_scope();
+    let future = Scoped {
+        scope,
+        future: Box::pin(future),
+    };
+    let id = spawn(Box::pin(future));
+    on_cleanup(move || {
+        TASKS.with(|tasks| tasks.borrow_mut().remove(id));
+    });
+}
+
+/// A future that is polled inside `scope`, so that signals and contexts it uses belong to it.
+struct Scoped {
+    scope: NodeHandle,
+    future: Task,
+}
+
+impl Future for Scoped {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let scope = self.scope;
+        scope.run_in(|| self.future.as_mut().poll(cx))
+    }
+}
+
+/// The value of a future, stored in a signal. See [`create_resource`].
+pub struct Resource<T: 'static> {
+    value: Signal<Option<T>>,
+}
+
+impl<T> Resource<T> {
+    /// Returns the value of the future, or `None` while it is loading.
+    pub fn value(self) -> ReadSignal<Option<T>> {
+        *self.value
+    }
+
+    /// Returns `true` while the future has not completed.
+    pub fn is_loading(self) -> bool {
+        self.value.with(Option::is_none)
+    }
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Resource<T> {}
+
+/// Run `future` in the current scope and store its output in a [`Resource`] once it completes.
+///
+/// Inside a [suspense scope](create_suspense_scope), the scope is pending until `future`
+/// completes.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{futures::*, root::*};
+/// # let _ = create_root(|| {
+/// let resource = create_resource(async { 42 });
+/// assert!(!resource.is_loading());
+/// assert_eq!(resource.value().get(), Some(42));
+/// # });
+/// ```
+pub fn create_resource<T: 'static>(future: impl Future<Output = T> + 'static) -> Resource<T> {
+    let value = create_signal(None);
+    let suspense = try_use_context::<SuspenseState>();
+    if let Some(suspense) = suspense {
+        suspense.pending.update(|pending| *pending += 1);
+        // Also stop waiting if the resource is disposed before it completes.
+        on_cleanup(move || {
+            if value.is_alive()
+                && value.with_untracked(Option::is_none)
+                && suspense.pending.is_alive()
+            {
+                suspense.pending.update(|pending| *pending -= 1);
+            }
+        });
+    }
+    spawn_local_scoped(async move {
+        value.set(Some(future.await));
+        if let Some(suspense) = suspense {
+            suspense.pending.update(|pending| *pending -= 1);
+        }
+    });
+    Resource { value }
+}
+
+/// Tracks the resources created inside a suspense scope. See [`create_suspense_scope`].
+#[derive(Clone, Copy)]
+pub struct SuspenseState {
+    pending: Signal<usize>,
+}
+
+impl SuspenseState {
+    /// Returns `true` while some resources created in the scope have not completed.
+    pub fn is_pending(self) -> bool {
+        self.pending.get() > 0
+    }
+}
+
+/// Run `f` in a new child scope that keeps track of the [resources](create_resource) created
+/// inside of it, including in nested scopes.
+///
+/// # Example
+/// ```
+/// # use rsx_reactive::{futures::*, root::*};
+/// # let _ = create_root(|| {
+/// let (_, suspense) = create_suspense_scope(|| create_resource(std::future::pending::<()>()));
+/// assert!(suspense.is_pending());
+/// let (_, suspense) = create_suspense_scope(|| create_resource(async { 42 }));
+/// assert!(!suspense.is_pending());
+/// # });
+/// ```
+pub fn create_suspense_scope<T>(f: impl FnOnce() -> T) -> (T, SuspenseState) {
+    let state = SuspenseState {
+        pending: create_signal(0),
+    };
+    let ret = provide_context_in_new_scope(state, f);
+    (ret, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, sync::mpsc};
+
+    use super::*;
+    use crate::root::create_root;
+
+    /// Returns a future resolving to `value`, sent from another thread.
+    fn from_thread<T: Send + 'static>(value: T) -> impl Future<Output = T> {
+        let (tx, rx) = mpsc::channel();
+        let mut value = Some(value);
+        poll_fn(move |cx| match rx.try_recv() {
+            Ok(value) => Poll::Ready(value),
+            Err(_) => {
+                if let Some(value) = value.take() {
+                    let (tx, waker) = (tx.clone(), cx.waker().clone());
+                    thread::spawn(move || {
+                        tx.send(value).unwrap();
+                        waker.wake();
+                    });
+                }
+                Poll::Pending
+            }
+        })
+    }
+
+    #[test]
+    fn poll_task_woken_from_another_thread() {
+        let _ = create_root(|| {
+            let resource = create_resource(from_thread(42));
+            assert!(resource.is_loading());
+            // Only the waker of the task is woken, from the other thread.
+            block_on(poll_fn(|_| match resource.is_loading() {
+                true => Poll::Pending,
+                false => Poll::Ready(()),
+            }));
+            assert_eq!(resource.value().get(), Some(42));
+        });
+    }
+
+    #[test]
+    fn block_on_timeout_gives_up() {
+        let value = block_on_timeout(std::future::pending::<()>(), Duration::from_millis(10));
+        assert_eq!(value, None);
+        let value = block_on_timeout(from_thread(1), Duration::from_secs(10));
+        assert_eq!(value, Some(1));
+    }
+}
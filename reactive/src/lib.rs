@@ -1,5 +1,9 @@
+pub mod context;
 pub mod effects;
+pub mod futures;
 pub mod memos;
+pub mod motion;
 pub mod node;
 pub mod root;
 pub mod signals;
+pub mod timers;
@@ -58,7 +58,7 @@ pub fn create_selector_with<T>(
 /// twice.
 ///
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(0);
 /// let double = || state.get() * 2;
@@ -78,7 +78,7 @@ pub fn create_selector_with<T>(
 ///
 /// # Example
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(0);
 /// let double = create_memo(move || state.get() * 2);
@@ -102,7 +102,7 @@ pub fn create_memo<T>(f: impl FnMut() -> T + 'static) -> ReadSignal<T> {
 ///
 /// # Example
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(1);
 /// let squared = create_selector(move || state.get() * state.get());
@@ -139,7 +139,7 @@ where
 ///
 /// # Example
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// enum Msg {
 ///     Increment,
 ///     Decrement,
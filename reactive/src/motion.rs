@@ -0,0 +1,440 @@
+//! Animated values.
+//!
+//! [Tweened](create_tweened_signal) and [spring](create_spring) signals move towards the value
+//! they are set to over several frames instead of changing at once. Frames are requested from the
+//! [`FrameScheduler`] of the current thread, which is `requestAnimationFrame` in the browser. In
+//! tests, a [`ManualClock`] runs the frames on demand, along with [timers](crate::timers):
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use rsx_reactive::{motion::*, root::create_root};
+//! # let _ = create_root(|| {
+//! let clock = ManualClock::new();
+//! set_frame_scheduler(clock.clone());
+//!
+//! let width = create_tweened_signal(0.0, Duration::from_millis(100), easing::linear);
+//! width.set(100.0);
+//! clock.advance(Duration::ZERO);
+//! clock.advance(Duration::from_millis(25));
+//! assert_eq!(width.get(), 25.0);
+//! clock.advance(Duration::from_millis(100));
+//! assert_eq!(width.get(), 100.0);
+//! assert!(!width.is_animating().get());
+//! # });
+//! ```
+//!
+//! Without a scheduler, e.g. when rendering on the server, the values jump to their target.
+
+use std::{cell::RefCell, ops::Deref, rc::Rc, time::Duration};
+
+use crate::{
+    signals::{ReadSignal, Signal, create_signal},
+    timers::{Clock, TimeoutId},
+};
+
+/// Runs callbacks before the next frame is painted.
+pub trait FrameScheduler {
+    /// Call `callback` once before the next frame, with the time of the frame in milliseconds.
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>);
+}
+
+thread_local! {
+    static SCHEDULER: RefCell<Option<Rc<dyn FrameScheduler>>> = const { RefCell::new(None) };
+}
+
+/// Set the frame scheduler of the current thread, replacing the previous one.
+pub fn set_frame_scheduler(scheduler: impl FrameScheduler + 'static) {
+    SCHEDULER.with(|current| *current.borrow_mut() = Some(Rc::new(scheduler)));
+}
+
+/// Returns `true` if a frame scheduler was set on the current thread.
+pub fn has_frame_scheduler() -> bool {
+    SCHEDULER.with(|current| current.borrow().is_some())
+}
+
+/// Call `callback` before the next frame, with the time of the frame in milliseconds. Returns
+/// `false` and drops the callback if no frame scheduler was set.
+pub fn request_frame(callback: impl FnOnce(f64) + 'static) -> bool {
+    let Some(scheduler) = SCHEDULER.with(|current| current.borrow().clone()) else {
+        return false;
+    };
+    scheduler.request_frame(Box::new(callback));
+    true
+}
+
+/// A timeout of a [`ManualClock`].
+struct Timeout {
+    id: TimeoutId,
+    /// The time at which the timeout fires, in milliseconds.
+    due: f64,
+    callback: Box<dyn FnOnce()>,
+}
+
+#[derive(Default)]
+struct ClockState {
+    now: f64,
+    callbacks: Vec<Box<dyn FnOnce(f64)>>,
+    /// Pending timeouts, in the order they were set.
+    timeouts: Vec<Timeout>,
+    next_id: TimeoutId,
+}
+
+/// A [`FrameScheduler`] and timer [`Clock`] that only moves when it is [advanced](Self::advance).
+/// Cloning it returns a handle to the same clock.
+#[derive(Clone, Default)]
+pub struct ManualClock(Rc<RefCell<ClockState>>);
+
+impl ManualClock {
+    /// Create a clock starting at 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current time in milliseconds.
+    pub fn now(&self) -> f64 {
+        self.0.borrow().now
+    }
+
+    /// Move the clock forward by `elapsed`, firing the timeouts that are due in order, and run
+    /// one frame. Frames requested while it runs are left for the next one.
+    pub fn advance(&self, elapsed: Duration) {
+        let end = self.now() + elapsed.as_secs_f64() * 1000.0;
+        loop {
+            let mut state = self.0.borrow_mut();
+            // The earliest timeout, or the first one set if several are due at the same time.
+            let next = (state.timeouts.iter().enumerate())
+                .filter(|(_, timeout)| timeout.due <= end)
+                .min_by(|(_, a), (_, b)| a.due.total_cmp(&b.due))
+                .map(|(index, _)| index);
+            let Some(index) = next else {
+                break;
+            };
+            let timeout = state.timeouts.remove(index);
+            state.now = timeout.due;
+            drop(state);
+            (timeout.callback)();
+        }
+        let (now, callbacks) = {
+            let mut state = self.0.borrow_mut();
+            state.now = end;
+            (state.now, std::mem::take(&mut state.callbacks))
+        };
+        for callback in callbacks {
+            callback(now);
+        }
+    }
+
+    /// Returns `true` if a frame was requested since the last one ran.
+    pub fn has_pending_frame(&self) -> bool {
+        !self.0.borrow().callbacks.is_empty()
+    }
+}
+
+impl FrameScheduler for ManualClock {
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>) {
+        self.0.borrow_mut().callbacks.push(callback);
+    }
+}
+
+impl Clock for ManualClock {
+    fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce()>) -> TimeoutId {
+        let mut state = self.0.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        let due = state.now + delay.as_secs_f64() * 1000.0;
+        state.timeouts.push(Timeout { id, due, callback });
+        id
+    }
+
+    fn clear_timeout(&self, id: TimeoutId) {
+        self.0
+            .borrow_mut()
+            .timeouts
+            .retain(|timeout| timeout.id != id);
+    }
+}
+
+/// Values that can be [tweened](create_tweened_signal).
+pub trait Animate: Clone + 'static {
+    /// Returns the value at `t` between `self` (0) and `to` (1). Easing functions may return
+    /// values outside of that range to overshoot.
+    fn lerp(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Animate for f64 {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animate for f32 {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t as f32
+    }
+}
+
+impl<T: Animate, const N: usize> Animate for [T; N] {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| self[i].lerp(&to[i], t))
+    }
+}
+
+impl<A: Animate, B: Animate> Animate for (A, B) {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        (self.0.lerp(&to.0, t), self.1.lerp(&to.1, t))
+    }
+}
+
+/// Easing functions, mapping the progress of a tween from 0 to 1 to the progress of its value.
+pub mod easing {
+    /// Constant speed.
+    pub fn linear(t: f64) -> f64 {
+        t
+    }
+
+    /// Starts slow and speeds up.
+    pub fn ease_in_cubic(t: f64) -> f64 {
+        t * t * t
+    }
+
+    /// Starts fast and slows down.
+    pub fn ease_out_cubic(t: f64) -> f64 {
+        1.0 - (1.0 - t).powi(3)
+    }
+
+    /// Starts and ends slow.
+    pub fn ease_in_out_cubic(t: f64) -> f64 {
+        match t < 0.5 {
+            true => 4.0 * t * t * t,
+            false => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A signal moving to the values it is set to over a fixed duration. See
+/// [`create_tweened_signal`].
+pub struct Tweened<T: 'static> {
+    value: Signal<T>,
+    target: Signal<T>,
+    /// Incremented by every [`set`](Tweened::set), stopping the previous animation.
+    generation: Signal<u64>,
+    animating: Signal<bool>,
+    duration: Duration,
+    easing: fn(f64) -> f64,
+}
+
+impl<T> Clone for Tweened<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Tweened<T> {}
+
+/// Create a signal starting at `initial` that moves to the values it is [set](Tweened::set) to
+/// over `duration`, with its progress shaped by `easing`, e.g. [`easing::ease_out_cubic`].
+pub fn create_tweened_signal<T: Animate>(
+    initial: T,
+    duration: Duration,
+    easing: fn(f64) -> f64,
+) -> Tweened<T> {
+    Tweened {
+        value: create_signal(initial.clone()),
+        target: create_signal(initial),
+        generation: create_signal(0),
+        animating: create_signal(false),
+        duration,
+        easing,
+    }
+}
+
+impl<T: Animate> Tweened<T> {
+    /// Start moving to `target` from the current value.
+    pub fn set(self, target: T) {
+        self.target.set(target.clone());
+        let generation = self.generation.update_silent(|generation| {
+            *generation += 1;
+            *generation
+        });
+        let from = self.value.get_clone_untracked();
+        let scheduled = !self.duration.is_zero()
+            && request_frame(move |now| self.step(generation, from, now, now));
+        if scheduled {
+            self.animating.set(true);
+        } else {
+            self.jump(target);
+        }
+    }
+
+    /// Set the value to `value` at once, stopping the animation.
+    pub fn jump(self, value: T) {
+        self.generation.update_silent(|generation| *generation += 1);
+        self.target.set(value.clone());
+        self.value.set(value);
+        self.animating.set(false);
+    }
+
+    /// Returns the value the signal is moving to.
+    pub fn target(self) -> ReadSignal<T> {
+        *self.target
+    }
+
+    /// Returns `true` while the value is moving.
+    pub fn is_animating(self) -> ReadSignal<bool> {
+        *self.animating
+    }
+
+    fn step(self, generation: u64, from: T, start: f64, now: f64) {
+        // Stop if the signal was disposed or set again.
+        if !self.value.is_alive() || self.generation.get_untracked() != generation {
+            return;
+        }
+        let duration = self.duration.as_secs_f64() * 1000.0;
+        let t = ((now - start) / duration).clamp(0.0, 1.0);
+        let target = self.target.get_clone_untracked();
+        if t < 1.0 {
+            self.value.set(from.lerp(&target, (self.easing)(t)));
+            request_frame(move |now| self.step(generation, from, start, now));
+        } else {
+            self.value.set(target);
+            self.animating.set(false);
+        }
+    }
+}
+
+/// The current value.
+impl<T> Deref for Tweened<T> {
+    type Target = ReadSignal<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// A signal following the values it is set to like a damped spring. See [`create_spring`].
+#[derive(Clone, Copy)]
+pub struct Spring {
+    value: Signal<f64>,
+    target: Signal<f64>,
+    velocity: Signal<f64>,
+    /// Incremented whenever the spring is started or stopped, stopping the previous frames.
+    generation: Signal<u64>,
+    animating: Signal<bool>,
+    stiffness: f64,
+    damping: f64,
+}
+
+/// Create a signal starting at `initial` that moves to the values it is [set](Spring::set) to
+/// like a spring of unit mass. A higher `stiffness` moves faster and a lower `damping` bounces
+/// more, e.g. a stiffness of 170 and a damping of 26 settle quickly without bouncing.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use rsx_reactive::{motion::*, root::create_root};
+/// # let _ = create_root(|| {
+/// let clock = ManualClock::new();
+/// set_frame_scheduler(clock.clone());
+///
+/// let x = create_spring(0.0, 170.0, 26.0);
+/// x.set(1.0);
+/// clock.advance(Duration::ZERO);
+/// clock.advance(Duration::from_millis(16));
+/// assert!(x.get() > 0.0 && x.get() < 1.0);
+/// while clock.has_pending_frame() {
+///     clock.advance(Duration::from_millis(16));
+/// }
+/// assert_eq!(x.get(), 1.0);
+/// # });
+/// ```
+pub fn create_spring(initial: f64, stiffness: f64, damping: f64) -> Spring {
+    Spring {
+        value: create_signal(initial),
+        target: create_signal(initial),
+        velocity: create_signal(0.0),
+        generation: create_signal(0),
+        animating: create_signal(false),
+        stiffness,
+        damping,
+    }
+}
+
+/// The distance and speed under which a spring is considered at rest.
+const REST_THRESHOLD: f64 = 1e-3;
+
+/// The longest time step simulated, so that a spring does not jump after the page was hidden.
+const MAX_STEP_MS: f64 = 64.0;
+
+impl Spring {
+    /// Start moving to `target`, keeping the current velocity.
+    pub fn set(self, target: f64) {
+        self.target.set(target);
+        if self.animating.get_untracked() {
+            return;
+        }
+        let generation = self.next_generation();
+        if request_frame(move |now| self.step(generation, now, now)) {
+            self.animating.set(true);
+        } else {
+            self.jump(target);
+        }
+    }
+
+    /// Set the value to `value` at once, stopping the spring.
+    pub fn jump(self, value: f64) {
+        self.target.set(value);
+        self.value.set(value);
+        self.velocity.set(0.0);
+        self.next_generation();
+        self.animating.set(false);
+    }
+
+    /// Returns the value the spring is moving to.
+    pub fn target(self) -> ReadSignal<f64> {
+        *self.target
+    }
+
+    /// Returns `true` until the spring comes to rest.
+    pub fn is_animating(self) -> ReadSignal<bool> {
+        *self.animating
+    }
+
+    fn next_generation(self) -> u64 {
+        self.generation.update_silent(|generation| {
+            *generation += 1;
+            *generation
+        })
+    }
+
+    fn step(self, generation: u64, last: f64, now: f64) {
+        if !self.value.is_alive() || self.generation.get_untracked() != generation {
+            return;
+        }
+        let target = self.target.get_untracked();
+        let (mut x, mut v) = (self.value.get_untracked(), self.velocity.get_untracked());
+        // Integrate in steps of a millisecond, which keeps stiff springs stable.
+        let elapsed = (now - last).clamp(0.0, MAX_STEP_MS);
+        let steps = elapsed.ceil() as usize;
+        for _ in 0..steps {
+            let dt = elapsed / steps as f64 / 1000.0;
+            let acceleration = -self.stiffness * (x - target) - self.damping * v;
+            v += acceleration * dt;
+            x += v * dt;
+        }
+        if (x - target).abs() < REST_THRESHOLD && v.abs() < REST_THRESHOLD {
+            self.jump(target);
+            return;
+        }
+        self.velocity.set(v);
+        self.value.set(x);
+        request_frame(move |next| self.step(generation, now, next));
+    }
+}
+
+/// The current value.
+impl Deref for Spring {
+    type Target = ReadSignal<f64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
@@ -28,6 +28,7 @@ pub(crate) enum Mark {
 
 pub(crate) struct ReactiveNode {
     pub value: Option<Box<dyn Any>>,
+    #[allow(clippy::type_complexity)]
     pub callback: Option<Box<dyn FnMut(&mut Box<dyn Any>) -> bool>>,
     /// Nodes that are owned by this node.
     pub children: Vec<NodeId>,
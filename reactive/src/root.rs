@@ -300,7 +300,7 @@ impl DependencyTracker {
 ///
 /// # Example
 /// ```rust
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 ///
 /// create_root(|| {
 ///     let signal = create_signal(123);
@@ -350,7 +350,7 @@ pub fn create_child_scope(f: impl FnOnce()) -> NodeHandle {
 ///
 /// # Example
 /// ```rust
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let child_scope = create_child_scope(|| {
 ///     on_cleanup(|| {
@@ -376,7 +376,7 @@ pub fn on_cleanup(f: impl FnOnce() + 'static) {
 /// # Example
 ///
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # let _ = create_root(|| {
 /// let state = create_signal(1);
 /// let double = create_memo(move || state.get() * 2);
@@ -402,7 +402,7 @@ pub fn batch<T>(f: impl FnOnce() -> T) -> T {
 /// # Example
 ///
 /// ```
-/// # use sycamore_reactive::*;
+/// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
 /// # create_root(|| {
 /// let state = create_signal(1);
 /// let double = create_memo(move || untrack(|| state.get() * 2));
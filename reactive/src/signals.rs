@@ -8,6 +8,7 @@ use std::ops::{AddAssign, Deref, DivAssign, MulAssign, RemAssign, SubAssign};
 use slotmap::Key;
 use smallvec::SmallVec;
 
+use crate::memos::create_memo;
 use crate::node::Mark;
 use crate::node::NodeHandle;
 use crate::node::NodeId;
@@ -73,7 +74,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// let doubled = create_memo(move || state.get() * 2);
@@ -91,7 +92,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// let doubled = create_memo(move || state.get() * 2);
@@ -117,7 +118,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(123);
     /// let prev = state.replace(456);
@@ -145,7 +146,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(Some(123));
     /// let prev = state.take();
@@ -186,11 +187,11 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal("Hello".to_string());
-    /// state.update(|val| val.push_str(" Sycamore!"));
-    /// assert_eq!(state.get_clone(), "Hello Sycamore!");
+    /// state.update(|val| val.push_str(" RSX!"));
+    /// assert_eq!(state.get_clone(), "Hello RSX!");
     /// # });
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
@@ -212,7 +213,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(123);
     /// state.set_fn(|val| *val + 1);
@@ -228,7 +229,7 @@ impl<T> Signal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let (read_signal, mut write_signal) = create_signal(0).split();
     /// assert_eq!(read_signal.get(), 0);
@@ -297,7 +298,7 @@ impl<T> ReadSignal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// // Note that we have used `get_untracked` here so the signal is not actually being tracked
@@ -333,7 +334,7 @@ impl<T> ReadSignal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// assert_eq!(state.get(), 0);
@@ -362,7 +363,7 @@ impl<T> ReadSignal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let greeting = create_signal("Hello".to_string());
     /// assert_eq!(greeting.get_clone(), "Hello".to_string());
@@ -393,8 +394,7 @@ impl<T> ReadSignal<T> {
             .value
             .as_ref()
             .expect("cannot read signal while updating");
-        let ret = f(value.downcast_ref().expect("wrong signal type"));
-        ret
+        f(value.downcast_ref().expect("wrong signal type"))
     }
 
     /// Get a value from the signal.
@@ -411,7 +411,7 @@ impl<T> ReadSignal<T> {
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// let doubled = state.map(|val| *val * 2);
@@ -420,17 +420,17 @@ impl<T> ReadSignal<T> {
     /// assert_eq!(doubled.get(), 2);
     /// # });
     /// ```
-    // #[cfg_attr(debug_assertions, track_caller)]
-    // pub fn map<U>(self, mut f: impl FnMut(&T) -> U + 'static) -> ReadSignal<U> {
-    //     create_memo(move || self.with(&mut f))
-    // }
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn map<U>(self, mut f: impl FnMut(&T) -> U + 'static) -> ReadSignal<U> {
+        create_memo(move || self.with(&mut f))
+    }
 
     /// Track the signal in the current reactive scope. This is done automatically when calling
     /// [`ReadSignal::get`] and other similar methods.
     ///
     /// # Example
     /// ```
-    /// # use sycamore_reactive::*;
+    /// # use rsx_reactive::{effects::*, memos::*, root::*, signals::*};
     /// # create_root(|| {
     /// let state = create_signal(0);
     /// create_effect(move || {
@@ -0,0 +1,256 @@
+//! Timers bound to the current scope.
+//!
+//! Timers are set on the [`Clock`] of the current thread, which uses `setTimeout` in the browser,
+//! and are cancelled when the scope they were created in is disposed. In tests, a
+//! [`ManualClock`](crate::motion::ManualClock) fires them on demand:
+//!
+//! ```
+//! # use std::{cell::Cell, rc::Rc, time::Duration};
+//! # use rsx_reactive::{motion::ManualClock, root::create_root, timers::*};
+//! let clock = ManualClock::new();
+//! set_clock(clock.clone());
+//!
+//! let ticks = Rc::new(Cell::new(0));
+//! let root = create_root({
+//!     let ticks = ticks.clone();
+//!     move || {
+//!         create_interval(Duration::from_secs(1), move || ticks.set(ticks.get() + 1));
+//!     }
+//! });
+//! clock.advance(Duration::from_millis(2500));
+//! assert_eq!(ticks.get(), 2);
+//!
+//! root.dispose();
+//! clock.advance(Duration::from_secs(10));
+//! assert_eq!(ticks.get(), 2);
+//! ```
+//!
+//! Without a clock, e.g. when rendering on the server, timers never fire, while
+//! [debounced](create_debounced) and [throttled](create_throttled) signals follow their source at
+//! once.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use crate::{
+    effects::create_effect,
+    root::on_cleanup,
+    signals::{ReadSignal, create_signal},
+};
+
+/// The id of a timeout, as returned by `setTimeout`.
+pub type TimeoutId = i32;
+
+/// Runs callbacks after a delay.
+pub trait Clock {
+    /// Call `callback` once `delay` has elapsed.
+    fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce()>) -> TimeoutId;
+
+    /// Cancel a timeout that has not fired yet.
+    fn clear_timeout(&self, id: TimeoutId);
+}
+
+thread_local! {
+    static CLOCK: RefCell<Option<Rc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Set the clock of the current thread, replacing the previous one.
+pub fn set_clock(clock: impl Clock + 'static) {
+    CLOCK.with(|current| *current.borrow_mut() = Some(Rc::new(clock)));
+}
+
+/// Returns `true` if a clock was set on the current thread.
+pub fn has_clock() -> bool {
+    CLOCK.with(|current| current.borrow().is_some())
+}
+
+fn clock() -> Option<Rc<dyn Clock>> {
+    CLOCK.with(|current| current.borrow().clone())
+}
+
+/// A timer created by [`create_timeout`] or [`create_interval`]. Cloning it returns a handle to
+/// the same timer.
+#[derive(Clone, Default)]
+pub struct Timer(Rc<Cell<Option<TimeoutId>>>);
+
+impl Timer {
+    /// Cancel the timer. This does nothing if it already fired or was cancelled.
+    pub fn cancel(&self) {
+        if let Some(id) = self.0.take()
+            && let Some(clock) = clock()
+        {
+            clock.clear_timeout(id);
+        }
+    }
+
+    /// Returns `true` until the timer is cancelled, or fires for a timeout.
+    pub fn is_pending(&self) -> bool {
+        self.0.get().is_some()
+    }
+
+    /// Replace the pending timeout with one calling `f` after `delay`.
+    fn restart(&self, delay: Duration, f: impl FnOnce() + 'static) {
+        self.cancel();
+        let Some(clock) = clock() else {
+            return;
+        };
+        let id = self.0.clone();
+        let callback = Box::new(move || {
+            id.set(None);
+            f();
+        });
+        self.0.set(Some(clock.set_timeout(delay, callback)));
+    }
+}
+
+/// Call `f` once `delay` has elapsed, unless the current scope is disposed first.
+pub fn create_timeout(delay: Duration, f: impl FnOnce() + 'static) -> Timer {
+    let timer = Timer::default();
+    timer.restart(delay, f);
+    on_cleanup({
+        let timer = timer.clone();
+        move || timer.cancel()
+    });
+    timer
+}
+
+/// Call `f` every `period` until the current scope is disposed.
+pub fn create_interval(period: Duration, f: impl FnMut() + 'static) -> Timer {
+    fn tick(timer: Timer, period: Duration, f: Rc<RefCell<dyn FnMut()>>) {
+        timer.clone().restart(period, move || {
+            // Schedule the next tick first so that `f` can cancel the interval.
+            tick(timer, period, f.clone());
+            (f.borrow_mut())();
+        });
+    }
+
+    let timer = Timer::default();
+    tick(timer.clone(), period, Rc::new(RefCell::new(f)));
+    on_cleanup({
+        let timer = timer.clone();
+        move || timer.cancel()
+    });
+    timer
+}
+
+/// Returns a signal following `source` once it has stopped changing for `delay`, e.g. to search
+/// as the user types without sending a request for every key.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use rsx_reactive::{motion::ManualClock, root::create_root, signals::*, timers::*};
+/// # let _ = create_root(|| {
+/// let clock = ManualClock::new();
+/// set_clock(clock.clone());
+///
+/// let query = create_signal(String::new());
+/// let debounced = create_debounced(*query, Duration::from_millis(300));
+/// query.set("r".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// query.set("rs".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// assert_eq!(debounced.get_clone(), "");
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(debounced.get_clone(), "rs");
+/// # });
+/// ```
+pub fn create_debounced<T: Clone + 'static>(
+    source: ReadSignal<T>,
+    delay: Duration,
+) -> ReadSignal<T> {
+    let output = create_signal(source.get_clone_untracked());
+    let timer = Timer::default();
+    let first = Cell::new(true);
+    create_effect({
+        let timer = timer.clone();
+        move || {
+            let value = source.get_clone();
+            if first.replace(false) {
+                return;
+            }
+            match has_clock() {
+                true => timer.restart(delay, move || output.set(value)),
+                false => output.set(value),
+            }
+        }
+    });
+    on_cleanup(move || timer.cancel());
+    *output
+}
+
+/// Returns a signal following `source` at most once every `interval`. A change is passed on at
+/// once if the previous one was passed on more than `interval` ago, and otherwise at the end of
+/// the interval, skipping the changes in between.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use rsx_reactive::{motion::ManualClock, root::create_root, signals::*, timers::*};
+/// # let _ = create_root(|| {
+/// let clock = ManualClock::new();
+/// set_clock(clock.clone());
+///
+/// let scroll = create_signal(0);
+/// let throttled = create_throttled(*scroll, Duration::from_millis(100));
+/// scroll.set(10);
+/// assert_eq!(throttled.get(), 10);
+/// scroll.set(20);
+/// scroll.set(30);
+/// assert_eq!(throttled.get(), 10);
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(throttled.get(), 30);
+/// # });
+/// ```
+pub fn create_throttled<T: Clone + 'static>(
+    source: ReadSignal<T>,
+    interval: Duration,
+) -> ReadSignal<T> {
+    let output = create_signal(source.get_clone_untracked());
+    let timer = Timer::default();
+    // The latest change that was not passed on yet.
+    let pending = Rc::new(RefCell::new(None::<T>));
+
+    // Wait for the end of the interval, then pass on the pending change if there is one and wait
+    // again.
+    fn cool_down<T: 'static>(
+        timer: Timer,
+        interval: Duration,
+        pending: Rc<RefCell<Option<T>>>,
+        emit: Rc<dyn Fn(T)>,
+    ) {
+        timer.clone().restart(interval, move || {
+            let value = pending.borrow_mut().take();
+            if let Some(value) = value {
+                emit(value);
+                cool_down(timer, interval, pending, emit);
+            }
+        });
+    }
+
+    let emit: Rc<dyn Fn(T)> = Rc::new(move |value| {
+        if output.is_alive() {
+            output.set(value);
+        }
+    });
+    let first = Cell::new(true);
+    create_effect({
+        let timer = timer.clone();
+        move || {
+            let value = source.get_clone();
+            if first.replace(false) {
+                return;
+            }
+            match timer.is_pending() {
+                true => *pending.borrow_mut() = Some(value),
+                false => {
+                    emit(value);
+                    cool_down(timer.clone(), interval, pending.clone(), emit.clone());
+                }
+            }
+        }
+    });
+    on_cleanup(move || timer.cancel());
+    *output
+}
@@ -0,0 +1,239 @@
+//! Route patterns and path matching.
+//!
+//! This crate does not touch the DOM, so routes can be matched anywhere, including on the server
+//! and in native tests. It is shared by `rsx-web`, which matches paths at runtime, and by the
+//! `Route` derive of `rsx-macros`, which checks patterns at compile time, so both always agree on
+//! what a valid pattern is.
+
+use std::fmt;
+
+/// A segment of a [`RoutePattern`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    /// Matches this exact segment.
+    Static(String),
+    /// `<name>`: matches any single segment.
+    Param(String),
+    /// `<name..>`: matches all the remaining segments, possibly none.
+    Rest(String),
+}
+
+/// A route pattern such as `/posts/<id>` or `/files/<path..>`.
+///
+/// Patterns are made of `/`-separated segments, each of which is either matched exactly, a
+/// parameter (`<name>`) matching any single segment, or, as the last segment only, a wildcard
+/// (`<name..>`) matching everything that is left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoutePattern {
+    segments: Vec<Segment>,
+}
+
+/// An error returned when parsing an invalid [`RoutePattern`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternError(String);
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl RoutePattern {
+    /// Parse a route pattern.
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let mut segments = Vec::new();
+        for segment in path_segments(pattern) {
+            if let Some(Segment::Rest(name)) = segments.last() {
+                return Err(PatternError(format!(
+                    "wildcard `<{name}..>` must be the last segment of `{pattern}`"
+                )));
+            }
+            let segment = match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+                Some(name) => match name.strip_suffix("..") {
+                    Some(name) => Segment::Rest(param_name(name, pattern)?),
+                    None => Segment::Param(param_name(name, pattern)?),
+                },
+                None if segment.contains(['<', '>']) => {
+                    return Err(PatternError(format!(
+                        "invalid segment `{segment}` in `{pattern}`, parameters must span a whole \
+                         segment"
+                    )));
+                }
+                None => Segment::Static(segment.to_string()),
+            };
+            if let Segment::Param(name) | Segment::Rest(name) = &segment
+                && segments.iter().any(|other| matches!(other, Segment::Param(other) | Segment::Rest(other) if other == name))
+            {
+                return Err(PatternError(format!(
+                    "duplicate parameter `{name}` in `{pattern}`"
+                )));
+            }
+            segments.push(segment);
+        }
+        Ok(Self { segments })
+    }
+
+    /// Returns the names of the parameters of this pattern, in order.
+    pub fn params(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Static(_) => None,
+            Segment::Param(name) | Segment::Rest(name) => Some(name.as_str()),
+        })
+    }
+
+    /// Match `path` against this pattern, returning the captured parameters if it matches.
+    ///
+    /// The query string and fragment of `path` are ignored, as are empty segments, so `/posts/`
+    /// and `/posts` are the same path.
+    pub fn matches(&self, path: &str) -> Option<Params> {
+        let segments = path_segments(path).collect::<Vec<_>>();
+        match self.match_prefix(&segments)? {
+            (params, consumed) if consumed == segments.len() => Some(params),
+            _ => None,
+        }
+    }
+
+    /// Match the start of `segments` against this pattern, returning the captured parameters and
+    /// the number of segments that were matched. This is how nested routes are matched.
+    pub fn match_prefix(&self, segments: &[&str]) -> Option<(Params, usize)> {
+        let mut rest = segments;
+        let mut params = Vec::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Static(expected) => {
+                    let (first, tail) = rest.split_first()?;
+                    if first != expected {
+                        return None;
+                    }
+                    rest = tail;
+                }
+                Segment::Param(name) => {
+                    let (first, tail) = rest.split_first()?;
+                    params.push((name.clone(), percent_decode(first)));
+                    rest = tail;
+                }
+                Segment::Rest(name) => {
+                    let value = rest.iter().map(|segment| percent_decode(segment));
+                    params.push((name.clone(), value.collect::<Vec<_>>().join("/")));
+                    rest = &[];
+                }
+            }
+        }
+        Some((Params(params), segments.len() - rest.len()))
+    }
+
+    /// Build the path matching this pattern with `params`, the reverse of
+    /// [`matches`](Self::matches). Fails if a parameter is missing.
+    ///
+    /// ```
+    /// # use rsx_route_pattern::{Params, RoutePattern};
+    /// let pattern = RoutePattern::parse("/posts/<id>").unwrap();
+    /// let params = Params::from_iter([("id", "42")]);
+    /// assert_eq!(pattern.format(&params).unwrap(), "/posts/42");
+    /// ```
+    pub fn format(&self, params: &Params) -> Result<String, PatternError> {
+        let mut path = String::new();
+        for segment in &self.segments {
+            let value = match segment {
+                Segment::Static(value) => value,
+                Segment::Param(name) | Segment::Rest(name) => params
+                    .get(name)
+                    .ok_or_else(|| PatternError(format!("missing parameter `{name}`")))?,
+            };
+            if !value.is_empty() {
+                path.push('/');
+                path.push_str(value);
+            }
+        }
+        if path.is_empty() {
+            path.push('/');
+        }
+        Ok(path)
+    }
+}
+
+/// Parameters captured by [`RoutePattern::matches`], percent-decoded. Wildcards capture the
+/// remaining segments joined with `/`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Returns the value of the parameter `name`. If there are several, e.g. because a nested
+    /// route reuses the name of a parameter of its parent, the last one is returned.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the names and values of the parameters.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl Extend<(String, String)> for Params {
+    fn extend<I: IntoIterator<Item = (String, String)>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+/// Returns the non-empty segments of the path of `url`, without the query string or fragment.
+pub fn path_segments(url: &str) -> impl Iterator<Item = &str> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+fn param_name(name: &str, pattern: &str) -> Result<String, PatternError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(PatternError(format!(
+            "invalid parameter name `{name}` in `{pattern}`, expected ASCII letters, digits and `_`"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Decode the `%XX` escapes of a path segment, e.g. `a%20b` into `a b`. Invalid escapes are kept
+/// as they are, and invalid UTF-8 is replaced.
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i + 1..i + 3) {
+            Some(hex) if bytes[i] == b'%' => std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
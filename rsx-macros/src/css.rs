@@ -0,0 +1,423 @@
+//! Parsing and scoping the stylesheets of `css!`.
+//!
+//! The stylesheet is validated and every selector is rewritten to only match elements with the
+//! scope class, e.g. `.item > a:hover` becomes `.item.rsx-1a2b3c4d > a.rsx-1a2b3c4d:hover`. Parts
+//! of a selector wrapped in `:global(...)` are left as they are.
+
+use quote::quote;
+use syn::LitStr;
+
+pub(crate) fn expand(input: LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let source = input.value();
+    let class = format!("rsx-{:08x}", hash(&source) as u32);
+    let css = scope(&source, &class).map_err(|Error { position, message }| {
+        let (line, column) = line_column(&source, position);
+        syn::Error::new(
+            input.span(),
+            format!("invalid CSS at line {line}, column {column}: {message}"),
+        )
+    })?;
+    Ok(quote! { ::rsx::css::ScopedStyle::new(#class, #css) })
+}
+
+/// Rewrite the stylesheet `source` so that its rules only apply to elements with `class`.
+pub(crate) fn scope(source: &str, class: &str) -> Result<String, Error> {
+    let source = strip_comments(source)?;
+    let mut parser = Parser {
+        source: &source,
+        position: 0,
+        class,
+        output: String::new(),
+    };
+    parser.rules(false)?;
+    Ok(parser.output)
+}
+
+pub(crate) struct Error {
+    /// The byte offset of the error in the source.
+    position: usize,
+    message: String,
+}
+
+fn error(position: usize, message: impl Into<String>) -> Error {
+    Error {
+        position,
+        message: message.into(),
+    }
+}
+
+/// FNV-1a, which is stable across compiler versions unlike `DefaultHasher`.
+fn hash(source: &str) -> u64 {
+    source.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn line_column(source: &str, position: usize) -> (usize, usize) {
+    let before = &source[..position];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Replace comments with spaces, keeping newlines so that positions are unchanged.
+fn strip_comments(source: &str) -> Result<String, Error> {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start + 2..].find("*/") else {
+            return Err(error(source.len() - rest.len() + start, "unclosed comment"));
+        };
+        let comment = &rest[start..start + end + 4];
+        output.extend(comment.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+        // Keep byte offsets identical for multi-byte characters.
+        output.extend(std::iter::repeat_n(
+            ' ',
+            comment.len() - comment.chars().count(),
+        ));
+        rest = &rest[start + end + 4..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// At-rules whose block contains rules, which are scoped as well.
+const GROUPING_AT_RULES: &[&str] = &["media", "supports", "container", "layer"];
+
+/// At-rules whose block contains declarations.
+const DECLARATION_AT_RULES: &[&str] = &["font-face", "page", "property"];
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    class: &'a str,
+    output: String,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Parse rules until the end of the source, or the end of the enclosing block if `nested`.
+    fn rules(&mut self, nested: bool) -> Result<(), Error> {
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None if nested => return Err(error(self.position, "expected `}`")),
+                None => return Ok(()),
+                Some('}') if nested => return Ok(()),
+                Some('}') => return Err(error(self.position, "unexpected `}`")),
+                Some('@') => self.at_rule()?,
+                Some(_) => self.style_rule()?,
+            }
+        }
+    }
+
+    fn style_rule(&mut self) -> Result<(), Error> {
+        let start = self.position;
+        let (prelude, end) = self.until(&['{', ';', '}'])?;
+        if end != Some('{') {
+            return Err(error(start, "expected `{` after the selector"));
+        }
+        let selectors =
+            scope_selectors(prelude.trim(), self.class).map_err(|message| error(start, message))?;
+        self.position += 1;
+        let declarations = self.declarations()?;
+        self.output
+            .push_str(&format!("{selectors}{{{declarations}}}"));
+        Ok(())
+    }
+
+    fn at_rule(&mut self) -> Result<(), Error> {
+        let start = self.position;
+        self.position += 1;
+        let name_length = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+            .unwrap_or(self.rest().len());
+        let name = self.rest()[..name_length].to_string();
+        if name.is_empty() {
+            return Err(error(start, "expected the name of the at-rule after `@`"));
+        }
+        self.position += name_length;
+        let (prelude, end) = self.until(&['{', ';', '}'])?;
+        let prelude = prelude.trim().to_string();
+        let head = match prelude.is_empty() {
+            true => format!("@{name}"),
+            false => format!("@{name} {prelude}"),
+        };
+        match end {
+            Some(';') => {
+                self.position += 1;
+                self.output.push_str(&format!("{head};"));
+            }
+            Some('{') => {
+                self.position += 1;
+                if GROUPING_AT_RULES.contains(&name.as_str()) {
+                    self.output.push_str(&format!("{head}{{"));
+                    self.rules(true)?;
+                    self.position += 1;
+                    self.output.push('}');
+                } else if DECLARATION_AT_RULES.contains(&name.as_str()) {
+                    let declarations = self.declarations()?;
+                    self.output.push_str(&format!("{head}{{{declarations}}}"));
+                } else if name.ends_with("keyframes") {
+                    // Keyframe selectors are percentages, which are not scoped.
+                    let body = self.block()?;
+                    self.output.push_str(&format!("{head}{{{body}}}"));
+                } else {
+                    return Err(error(start, format!("unsupported at-rule `@{name}`")));
+                }
+            }
+            _ => {
+                return Err(error(
+                    start,
+                    format!("expected `{{` or `;` after `@{name}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the declarations of a block up to its closing brace, which is consumed.
+    fn declarations(&mut self) -> Result<String, Error> {
+        let mut output = String::new();
+        loop {
+            self.skip_whitespace();
+            let start = self.position;
+            let (declaration, end) = self.until(&[';', '{', '}'])?;
+            let declaration = declaration.trim();
+            if !declaration.is_empty() {
+                output.push_str(&declaration_to_string(declaration, start)?);
+            }
+            match end {
+                Some(';') => {
+                    self.position += 1;
+                }
+                Some('}') => {
+                    self.position += 1;
+                    // The last declaration does not need a semicolon.
+                    if output.ends_with(';') {
+                        output.pop();
+                    }
+                    return Ok(output);
+                }
+                Some(_) => return Err(error(start, "nested rules are not supported")),
+                None => return Err(error(self.position, "expected `}`")),
+            }
+        }
+    }
+
+    /// Returns the content of a block up to its matching closing brace, which is consumed.
+    fn block(&mut self) -> Result<String, Error> {
+        let start = self.position;
+        let mut depth = 0;
+        loop {
+            let (_, end) = self.until(&['{', '}'])?;
+            match end {
+                Some('{') => depth += 1,
+                Some(_) if depth > 0 => depth -= 1,
+                Some(_) => break,
+                None => return Err(error(self.position, "expected `}`")),
+            }
+            self.position += 1;
+        }
+        let body = self.source[start..self.position].split_whitespace();
+        self.position += 1;
+        Ok(body.collect::<Vec<_>>().join(" "))
+    }
+
+    /// Advance to the next of `stops` outside of strings and parentheses, returning the text
+    /// before it and the stop found, if any. The stop itself is not consumed.
+    fn until(&mut self, stops: &[char]) -> Result<(&'a str, Option<char>), Error> {
+        let (source, start) = (self.source, self.position);
+        let mut depth = 0usize;
+        let mut chars = source[start..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' | '\'' => {
+                    let mut closed = false;
+                    while let Some((_, next)) = chars.next() {
+                        match next {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '\n' => break,
+                            next if next == c => {
+                                closed = true;
+                                break;
+                            }
+                            _ => {}
+                        }
+                    }
+                    if !closed {
+                        return Err(error(start + offset, "unclosed string"));
+                    }
+                }
+                '(' => depth += 1,
+                ')' if depth == 0 => return Err(error(start + offset, "unexpected `)`")),
+                ')' => depth -= 1,
+                c if depth == 0 && stops.contains(&c) => {
+                    self.position = start + offset;
+                    return Ok((&source[start..start + offset], Some(c)));
+                }
+                _ => {}
+            }
+        }
+        if depth > 0 {
+            return Err(error(source.len(), "expected `)`"));
+        }
+        self.position = source.len();
+        Ok((&source[start..], None))
+    }
+}
+
+/// Check and normalize a declaration, e.g. `color : red` to `color:red;`.
+fn declaration_to_string(declaration: &str, position: usize) -> Result<String, Error> {
+    let Some((property, value)) = declaration.split_once(':') else {
+        return Err(error(
+            position,
+            format!("expected `:` in the declaration `{declaration}`"),
+        ));
+    };
+    let (property, value) = (property.trim(), value.trim());
+    let custom = property.starts_with("--");
+    let valid = !property.is_empty()
+        && property
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(error(
+            position,
+            format!("invalid property name `{property}`"),
+        ));
+    }
+    if value.is_empty() && !custom {
+        return Err(error(
+            position,
+            format!("expected a value for the property `{property}`"),
+        ));
+    }
+    Ok(format!("{property}:{value};"))
+}
+
+/// Scope every selector of a comma-separated selector list.
+fn scope_selectors(selectors: &str, class: &str) -> Result<String, String> {
+    let scoped = split_top_level(selectors, |c| c == ',')
+        .into_iter()
+        .map(|selector| scope_selector(selector.trim(), class))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(scoped.join(","))
+}
+
+/// Scope every compound selector of a complex selector, e.g. both `ul` and `li` in `ul > li`.
+fn scope_selector(selector: &str, class: &str) -> Result<String, String> {
+    if selector.is_empty() {
+        return Err("expected a selector".to_string());
+    }
+    let mut output = String::new();
+    let mut combinator = None;
+    let mut compound = String::new();
+    let mut depth = 0usize;
+    let mut flush = |compound: &mut String, combinator: &mut Option<char>| {
+        if compound.is_empty() {
+            return Ok(());
+        }
+        if !output.is_empty() {
+            match combinator.take() {
+                Some(' ') | None => output.push(' '),
+                Some(c) => output.push_str(&format!(" {c} ")),
+            }
+        } else if let Some(c) = combinator.take()
+            && c != ' '
+        {
+            return Err(format!("unexpected combinator `{c}` in `{selector}`"));
+        }
+        output.push_str(&scope_compound(compound, class));
+        compound.clear();
+        Ok(())
+    };
+    for c in selector.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && (c.is_whitespace() || matches!(c, '>' | '+' | '~')) {
+            flush(&mut compound, &mut combinator)?;
+            if combinator.is_none_or(|combinator| combinator == ' ') {
+                combinator = Some(if c.is_whitespace() { ' ' } else { c });
+            } else if !c.is_whitespace() {
+                return Err(format!("unexpected combinator `{c}` in `{selector}`"));
+            }
+        } else {
+            compound.push(c);
+        }
+    }
+    flush(&mut compound, &mut combinator)?;
+    if let Some(c) = combinator.filter(|c| *c != ' ') {
+        return Err(format!("expected a selector after `{c}` in `{selector}`"));
+    }
+    Ok(output)
+}
+
+/// Add the scope class to a compound selector, before its pseudo-classes and pseudo-elements.
+fn scope_compound(compound: &str, class: &str) -> String {
+    if let Some(global) = compound
+        .strip_prefix(":global(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return global.to_string();
+    }
+    let mut depth = 0usize;
+    let pseudo = compound.char_indices().find_map(|(i, c)| {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => return Some(i),
+            _ => {}
+        }
+        None
+    });
+    let (before, after) = compound.split_at(pseudo.unwrap_or(compound.len()));
+    match before {
+        "*" => format!(".{class}{after}"),
+        _ => format!("{before}.{class}{after}"),
+    }
+}
+
+/// Split `text` at the characters matching `split` outside of parentheses and brackets.
+fn split_top_level(text: &str, split: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            c if depth == 0 && split(c) => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
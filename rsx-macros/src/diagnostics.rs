@@ -0,0 +1,50 @@
+//! Errors collected while parsing.
+//!
+//! The parser recovers from most mistakes instead of bailing out on the first one, so that all of
+//! them are reported at once and the rest of the template still expands. Recoverable errors are
+//! pushed here and turned into `compile_error!` invocations next to the expansion.
+
+use std::cell::RefCell;
+
+thread_local! {
+    static ERRORS: RefCell<Vec<syn::Error>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Report an error without aborting the parse.
+pub(crate) fn emit(error: syn::Error) {
+    ERRORS.with(|errors| errors.borrow_mut().push(error));
+}
+
+/// Take all the errors reported so far, combined into a single one.
+pub(crate) fn take() -> Option<syn::Error> {
+    ERRORS.take().into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    })
+}
+
+/// Returns the candidate closest to `name`, if it is close enough to be a likely typo.
+pub(crate) fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, candidate)| *distance <= candidate.len().div_ceil(3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,95 @@
+//! The events known to `rsx!`.
+//!
+//! This table is the only list of events: `rsx::events` generates its descriptors from it with
+//! [`expand`], the parser suggests names from it for misspelled events, and `rsx-web` delegates
+//! the events marked as delegated.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::diagnostics;
+
+/// The events of each `web_sys` event type.
+#[rustfmt::skip]
+const EVENTS: &[(&str, &[&str])] = &[
+    ("Event", &[
+        "abort", "cancel", "canplay", "canplaythrough", "change", "close", "cuechange",
+        "durationchange", "emptied", "ended", "error", "fullscreenchange", "fullscreenerror",
+        "invalid", "load", "loadeddata", "loadedmetadata", "loadstart", "pause", "play",
+        "playing", "ratechange", "reset", "resize", "scroll", "scrollend", "seeked", "seeking",
+        "select", "selectionchange", "selectstart", "stalled", "suspend", "timeupdate", "toggle",
+        "volumechange", "waiting",
+    ]),
+    ("AnimationEvent", &[
+        "animationcancel", "animationend", "animationiteration", "animationstart",
+    ]),
+    ("ClipboardEvent", &["copy", "cut", "paste"]),
+    ("CompositionEvent", &["compositionend", "compositionstart", "compositionupdate"]),
+    ("DragEvent", &["drag", "dragend", "dragenter", "dragleave", "dragover", "dragstart", "drop"]),
+    ("FocusEvent", &["blur", "focus", "focusin", "focusout"]),
+    ("InputEvent", &["beforeinput", "input"]),
+    ("KeyboardEvent", &["keydown", "keypress", "keyup"]),
+    ("MouseEvent", &[
+        "auxclick", "click", "contextmenu", "dblclick", "mousedown", "mouseenter", "mouseleave",
+        "mousemove", "mouseout", "mouseover", "mouseup",
+    ]),
+    ("PointerEvent", &[
+        "gotpointercapture", "lostpointercapture", "pointercancel", "pointerdown",
+        "pointerenter", "pointerleave", "pointermove", "pointerout", "pointerover", "pointerup",
+    ]),
+    ("ProgressEvent", &["progress"]),
+    ("SubmitEvent", &["submit"]),
+    ("TouchEvent", &["touchcancel", "touchend", "touchmove", "touchstart"]),
+    ("TransitionEvent", &[
+        "transitioncancel", "transitionend", "transitionrun", "transitionstart",
+    ]),
+    ("WheelEvent", &["wheel"]),
+];
+
+/// The events that bubble and can therefore be handled at the render root.
+#[rustfmt::skip]
+const DELEGATED: &[&str] = &[
+    "auxclick", "beforeinput", "change", "click", "compositionend", "compositionstart",
+    "compositionupdate", "contextmenu", "copy", "cut", "dblclick", "drag", "dragend",
+    "dragenter", "dragleave", "dragover", "dragstart", "drop", "focusin", "focusout", "input",
+    "keydown", "keypress", "keyup", "mousedown", "mousemove", "mouseout", "mouseover", "mouseup",
+    "paste", "pointercancel", "pointerdown", "pointermove", "pointerout", "pointerover",
+    "pointerup", "reset", "submit", "touchcancel", "touchend", "touchmove", "touchstart", "wheel",
+];
+
+/// Suggest a known event for the unknown event `name`. Unknown events are rejected when the
+/// expansion resolves their descriptor, so this only improves the error.
+pub(crate) fn suggest(name: &str) -> Option<&'static str> {
+    let names: Vec<_> = EVENTS
+        .iter()
+        .flat_map(|(_, names)| names.iter().copied())
+        .collect();
+    match names.contains(&name) {
+        true => None,
+        false => diagnostics::suggest(name, &names),
+    }
+}
+
+/// Invoke the macro `callback` with the table of events:
+///
+/// ```text
+/// callback! {
+///     events { web_sys::Event { abort, cancel, ... } web_sys::MouseEvent { click, ... } ... }
+///     delegated { click, ... }
+/// }
+/// ```
+pub(crate) fn expand(callback: syn::Path) -> TokenStream {
+    let ident = |name: &str| Ident::new(name, Span::call_site());
+    let groups = EVENTS.iter().map(|(ty, names)| {
+        let ty = ident(ty);
+        let names = names.iter().map(|name| ident(name));
+        quote!(web_sys::#ty { #(#names),* })
+    });
+    let delegated = DELEGATED.iter().map(|name| ident(name));
+    quote! {
+        #callback! {
+            events { #(#groups)* }
+            delegated { #(#delegated),* }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! `#[derive(Form)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`Form` can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Form` can only be derived for structs",
+            ));
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`Form` cannot be derived for generic structs",
+        ));
+    }
+
+    let (vis, name) = (&input.vis, &input.ident);
+    let fields_name = format_ident!("{name}Fields");
+    let idents = fields
+        .iter()
+        .flat_map(|field| &field.ident)
+        .collect::<Vec<_>>();
+    let tys = fields.iter().map(|field| &field.ty);
+    let field_vis = fields.iter().map(|field| &field.vis);
+    let doc = format!("The fields of a [`{name}`] form.");
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy)]
+        #vis struct #fields_name {
+            #(#field_vis #idents: ::rsx::form::Field<#tys>,)*
+        }
+
+        impl ::rsx::form::Form for #name {
+            type Fields = #fields_name;
+
+            fn create_fields(initial: Self) -> Self::Fields {
+                #fields_name {
+                    #(#idents: ::rsx::form::Field::new(initial.#idents),)*
+                }
+            }
+
+            fn values(fields: &Self::Fields) -> Self {
+                Self {
+                    #(#idents: fields.#idents.value.get_clone(),)*
+                }
+            }
+
+            fn field_states(fields: &Self::Fields) -> ::std::vec::Vec<::rsx::form::FieldState> {
+                ::std::vec![#(fields.#idents.state(::std::stringify!(#idents)),)*]
+            }
+        }
+    })
+}
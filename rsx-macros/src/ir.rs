@@ -1,25 +1,134 @@
-// use proc_macro2::Span;
-use syn::{Expr, Ident, LitStr};
+use proc_macro2::Span;
+use syn::{Expr, Ident, LitStr, Pat};
 
 pub(crate) struct Root(pub(crate) Vec<Node>);
 
+impl Root {
+    /// Propagate the `css` props of every element to its descendants, including those inside
+    /// control flow. Components are rendered by their own templates and are not affected.
+    pub(crate) fn inherit_scopes(&mut self, scopes: &[Expr]) {
+        for node in &mut self.0 {
+            node.inherit_scopes(scopes);
+        }
+    }
+}
+
+impl Node {
+    fn inherit_scopes(&mut self, scopes: &[Expr]) {
+        match self {
+            Node::Tag(tag) => {
+                tag.scopes = scopes.to_vec();
+                tag.scopes.extend(tag.props.iter().filter_map(|prop| {
+                    match (prop.identifier.as_str(), &prop.prop_type) {
+                        ("css", PropType::Expression { value }) => Some(value.clone()),
+                        _ => None,
+                    }
+                }));
+                for child in &mut tag.children {
+                    child.inherit_scopes(&tag.scopes);
+                }
+            }
+            Node::If(if_node) => {
+                let else_branch = if_node.else_branch.iter_mut();
+                for branch in if_node
+                    .branches
+                    .iter_mut()
+                    .map(|(_, b)| b)
+                    .chain(else_branch)
+                {
+                    branch.inherit_scopes(scopes);
+                }
+            }
+            Node::Match(match_node) => {
+                for arm in &mut match_node.arms {
+                    arm.body.inherit_scopes(scopes);
+                }
+            }
+            Node::For(for_node) => for_node.body.inherit_scopes(scopes),
+            Node::Reactive(node) => node.inherit_scopes(scopes),
+            Node::Text(_) | Node::Dynamic(_) => {}
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum Node {
     Tag(TagNode),
+    /// Adjacent text tokens and string literals, with the whitespace between them reconstructed
+    /// from the source.
     Text(String),
+    /// Syntax: `{<expr>}`. A braced string literal with `{<ident>}` placeholders, e.g.
+    /// `{"{count} items"}`, is expanded to `format!`.
     Dynamic(Expr),
+    /// Syntax: `{static if <cond> { <markup> } else if <cond> { <markup> } else { <markup> }}`.
+    /// Without `static`, the block is parsed as a [`Node::Reactive`] wrapping this node.
+    If(IfNode),
+    /// Syntax: `{static match <expr> { <pat> => { <markup> }, ... }}`, see [`Node::If`].
+    Match(MatchNode),
+    /// Syntax: `{static for <pat> in <expr> { <markup> }}`, see [`Node::If`].
+    For(ForNode),
+    /// Syntax: `{move || <expr>}`, or `{if ...}`, `{match ...}` and `{for ...}` without `static`.
+    /// Rendered again whenever the signals it reads change.
+    Reactive(Box<Node>),
+}
+
+pub(crate) struct IfNode {
+    /// The `if` and `else if` branches, in order.
+    pub(crate) branches: Vec<(Expr, Root)>,
+    pub(crate) else_branch: Option<Root>,
+}
+
+pub(crate) struct MatchNode {
+    pub(crate) expr: Expr,
+    pub(crate) arms: Vec<MatchArm>,
+}
+
+pub(crate) struct MatchArm {
+    pub(crate) pat: Pat,
+    pub(crate) guard: Option<Expr>,
+    pub(crate) body: Root,
+}
+
+pub(crate) struct ForNode {
+    pub(crate) pat: Pat,
+    pub(crate) iter: Expr,
+    pub(crate) body: Root,
 }
 
 pub(crate) struct TagNode {
     pub(crate) name: Ident,
     pub(crate) props: Vec<Prop>,
+    /// Syntax: `{..<expr>}`, in the order they appear.
+    pub(crate) spreads: Vec<Expr>,
     pub(crate) children: Vec<Node>,
+    /// The `css` props of this element and its ancestors in the same template, whose scope
+    /// classes are added to the element. Filled in by [`Root::inherit_scopes`].
+    pub(crate) scopes: Vec<Expr>,
 }
 
 pub(crate) struct Prop {
+    /// The name of the prop, e.g. `class` or `on:click`.
     pub(crate) identifier: String,
+    /// The span of the last identifier of the name, used for diagnostics.
+    pub(crate) span: Span,
+    /// Event modifiers, e.g. `once` and `capture` in `on:click|once|capture`.
+    pub(crate) modifiers: Vec<Ident>,
     pub(crate) prop_type: PropType,
 }
 
+/// The prop setting the inner HTML of an element, which cannot have children as well.
+pub(crate) const INNER_HTML: &str = "dangerously_set_inner_html";
+
+/// Returns the name of the event if `identifier` is an event listener, e.g. `click` for both
+/// `onclick` and `on:click`.
+pub(crate) fn event_name(identifier: &str) -> Option<&str> {
+    identifier
+        .strip_prefix("on:")
+        .or_else(|| identifier.strip_prefix("on"))
+        .filter(|event| !event.is_empty())
+}
+
+#[allow(clippy::large_enum_variant)]
 pub enum PropType {
     /// Syntax: `<name>="<expr>"`.
     Plain { value: LitStr },
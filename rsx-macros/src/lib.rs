@@ -1,16 +1,134 @@
 // mod codegen;
+mod css;
+mod diagnostics;
+mod events;
+mod form;
 mod ir;
 mod parse;
+mod route;
 mod tokens;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::parse_macro_input;
 
 use crate::ir::Root;
 
+/// Build an `rsx::Element` from HTML-like markup.
+///
+/// `{if ...}`, `{match ...}` and `{for ...}` blocks are rendered again whenever the signals they
+/// read change, e.g. `{if open.get() { ... }}` or `{for todo in todos.get_clone() { ... }}`. Like
+/// any closure that can be called more than once, they cannot move out of the values they capture.
+/// Prefixing a block with `static` renders it once instead, so that it can move the values it
+/// uses, e.g. `{static for todo in todos { ... }}` with an owned `Vec`. Expressions are rendered
+/// once unless they are prefixed with `move ||`: `{move || count.get() * 2}`.
+///
+/// The parser recovers from most mistakes, so every error in a template is reported at once. The
+/// parts of the template that could be parsed are still expanded next to the errors, which keeps
+/// type checking and completions working inside the rest of the template.
+///
+/// Unclosed tags are reported at the opening tag:
+///
+/// ```compile_fail
+/// # use rsx::rsx;
+/// let _ = rsx!(<div><p>Hello</div>);
+/// ```
+///
+/// `dangerously_set_inner_html` replaces the children of an element, so it cannot be combined with
+/// them:
+///
+/// ```compile_fail
+/// # use rsx::rsx;
+/// let _ = rsx!(<div dangerously_set_inner_html="<b>Hi</b>">Hello</div>);
+/// ```
 #[proc_macro]
 pub fn rsx(input: TokenStream) -> TokenStream {
-    let node = parse_macro_input!(input as Root);
-    quote! {{ rsx::Element::from(#node) }}.into()
+    let mut root = syn::parse::<Root>(input).unwrap_or_else(|error| {
+        diagnostics::emit(error);
+        Root(Vec::new())
+    });
+    root.inherit_scopes(&[]);
+    let errors = diagnostics::take().map(|error| error.to_compile_error());
+    quote! {{ #errors #root }}.into()
+}
+
+/// Create an `rsx::css::ScopedStyle` from a stylesheet.
+///
+/// The stylesheet is checked at compile time and its selectors are rewritten to only match
+/// elements with a class derived from its content. Passing the style to an element with
+/// `css={STYLE}` in `rsx!` adds that class to the element and to all the elements below it in the
+/// same template, and adds the stylesheet to the document once.
+///
+/// ```
+/// # use rsx::{css, css::ScopedStyle, rsx};
+/// const STYLE: ScopedStyle = css!(
+///     "
+///     .card { padding: 1rem; }
+///     .card > h2:hover { color: red; }
+///     "
+/// );
+/// let _ = rsx!(<div class="card" css={STYLE}><h2>Title</h2></div>);
+/// ```
+///
+/// Parts of a selector wrapped in `:global(...)` are not scoped, e.g. `:global(body) p`. The
+/// selectors of `@media`, `@supports`, `@container` and `@layer` blocks are scoped as well, while
+/// `@keyframes` are left as they are. Invalid stylesheets are compile errors:
+///
+/// ```compile_fail
+/// # use rsx::css;
+/// let _ = css!(".card { padding 1rem; }");
+/// ```
+#[proc_macro]
+pub fn css(input: TokenStream) -> TokenStream {
+    syn::parse(input)
+        .and_then(css::expand)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Invoke the given macro with the table of events, to generate `rsx::events`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __events(input: TokenStream) -> TokenStream {
+    syn::parse(input)
+        .map(events::expand)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derive `rsx_web::router::Route` for an enum of routes.
+///
+/// Every variant has either a `#[to("...")]` pattern or the `#[not_found]` attribute, which
+/// exactly one unit variant must have. Parameters are bound to the fields of the same name, or in
+/// order for tuple variants, and are parsed with `FromStr`. Patterns and fields are checked at
+/// compile time:
+///
+/// ```compile_fail
+/// # use rsx_web::router::Route;
+/// #[derive(Route)]
+/// enum AppRoute {
+///     #[to("/posts/<id>")]
+///     Post { slug: String },
+///     #[not_found]
+///     NotFound,
+/// }
+/// ```
+#[proc_macro_derive(Route, attributes(to, not_found))]
+pub fn derive_route(input: TokenStream) -> TokenStream {
+    let output = syn::parse(input)
+        .and_then(route::derive)
+        .unwrap_or_else(|error| error.to_compile_error());
+    let errors = diagnostics::take().map(|error| error.to_compile_error());
+    quote! { #errors #output }.into()
+}
+
+/// Derive `rsx::form::Form` for a struct with named fields.
+///
+/// This generates a `<Name>Fields` struct with an `rsx::form::Field` for every field, with the
+/// same name and visibility. Every field type must be `Clone + PartialEq`.
+#[proc_macro_derive(Form)]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    syn::parse(input)
+        .and_then(form::derive)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
 }
@@ -1,20 +1,205 @@
-use proc_macro2::TokenTree;
+use std::cell::RefCell;
+
+use proc_macro2::{LineColumn, Span, TokenTree};
 use syn::{
-    Ident, LitStr, Token, braced,
-    parse::{Parse, ParseStream},
+    Expr, ExprLit, Ident, Lit, LitStr, Pat, Token, braced,
+    buffer::Cursor,
+    ext::IdentExt,
+    parse::{Parse, ParseStream, discouraged::Speculative},
+    parse_quote,
     token::Brace,
 };
 
-use crate::ir::{Node, Prop, PropType, Root, TagNode};
+use crate::{
+    diagnostics, events,
+    ir::{
+        ForNode, INNER_HTML, IfNode, MatchArm, MatchNode, Node, Prop, PropType, Root, TagNode,
+        event_name,
+    },
+};
+
+thread_local! {
+    /// The names of the tags enclosing the one being parsed, innermost last.
+    static OPEN_TAGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
 
 impl Parse for Root {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut children = Vec::new();
-        while !input.is_empty() {
-            children.push(input.parse()?);
+        // A block is its own token group, so tags outside of it cannot be closed from inside.
+        let outer = OPEN_TAGS.take();
+        let children = parse_children(input, None, |input| input.is_empty());
+        OPEN_TAGS.set(outer);
+        Ok(Self(children?))
+    }
+}
+
+/// Run `parser` on a fork of `input`, only advancing `input` if it succeeds. On failure, the error
+/// is reported and `None` is returned so that the caller can skip to a point where parsing can
+/// resume.
+fn recover<T>(input: ParseStream, parser: impl FnOnce(ParseStream) -> syn::Result<T>) -> Option<T> {
+    let fork = input.fork();
+    match parser(&fork) {
+        Ok(value) => {
+            input.advance_to(&fork);
+            Some(value)
+        }
+        Err(error) => {
+            diagnostics::emit(error);
+            None
+        }
+    }
+}
+
+/// Skip at least one token, then every token until `stop` returns `true`.
+fn skip_until(input: ParseStream, stop: impl Fn(ParseStream) -> bool) {
+    let _ = input.parse::<TokenTree>();
+    while !input.is_empty() && !stop(input) {
+        let _ = input.parse::<TokenTree>();
+    }
+}
+
+/// Returns `true` if the next token starts a node other than text.
+fn peek_node(input: ParseStream) -> bool {
+    input.peek(Token![<]) || input.peek(Brace)
+}
+
+/// Parse an expression filling the whole of `content`.
+fn parse_expr(content: ParseStream) -> syn::Result<Expr> {
+    let expr = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("unexpected token, expected `}`"));
+    }
+    Ok(expr)
+}
+
+/// The whitespace between two tokens, reconstructed from their locations.
+enum Gap {
+    /// The tokens are adjacent.
+    None,
+    /// The tokens are on the same line, separated by this many columns.
+    Inline(usize),
+    /// The tokens are on different lines.
+    Newline,
+}
+
+impl Gap {
+    fn between(end: LineColumn, start: LineColumn) -> Self {
+        if start.line == 0 {
+            // Locations are not available, so assume the tokens are separated by a space.
+            Self::Inline(1)
+        } else if start.line != end.line {
+            Self::Newline
+        } else if start.column > end.column {
+            Self::Inline(start.column - end.column)
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Returns `true` if the next token is text rather than a tag or a braced block.
+fn peek_text(input: ParseStream) -> bool {
+    !peek_node(input)
+}
+
+/// Parse a single text token. String literals are unquoted, anything else is taken verbatim from
+/// the source.
+fn parse_text(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitStr) {
+        return Ok(input.parse::<LitStr>()?.value());
+    }
+    let token = input.parse::<TokenTree>()?;
+    Ok(token
+        .span()
+        .source_text()
+        .unwrap_or_else(|| token.to_string()))
+}
+
+/// Returns the span of the last token tree between `from` and `to`.
+fn last_span(mut from: Cursor, to: Cursor) -> Span {
+    let mut span = from.span();
+    while from != to {
+        let Some((token, next)) = from.token_tree() else {
+            break;
+        };
+        span = token.span();
+        from = next;
+    }
+    span
+}
+
+/// Parse the children of a tag (or of the macro input) until `is_end` returns `true`.
+///
+/// Adjacent text tokens are merged into a single text node and the whitespace between nodes is
+/// reconstructed from token locations, following the rules of JSX:
+/// - whitespace on a single line is kept as is, including between a text and a tag or a block;
+/// - text spanning several lines is joined with a single space;
+/// - whitespace containing a line break that is next to a tag or a block is removed.
+///
+/// `open` is the span of the token before the first child, if any. Children that fail to parse are
+/// reported and skipped.
+fn parse_children(
+    input: ParseStream,
+    open: Option<Span>,
+    is_end: impl Fn(ParseStream) -> bool,
+) -> syn::Result<Vec<Node>> {
+    let mut children = Vec::new();
+    // The text node being built, if the previous child was text.
+    let mut text: Option<String> = None;
+    let mut prev_end = open.map(|span| span.end());
+    let mut prev_is_text = false;
+
+    while !is_end(input) {
+        let is_text = peek_text(input);
+        let gap = match prev_end {
+            Some(end) => Gap::between(end, input.span().start()),
+            None => Gap::None,
+        };
+        match gap {
+            Gap::Inline(columns) => text.get_or_insert_default().push_str(&" ".repeat(columns)),
+            Gap::Newline if is_text && prev_is_text => text.get_or_insert_default().push(' '),
+            Gap::Newline | Gap::None => {}
         }
-        Ok(Self(children))
+
+        let start = input.cursor();
+        if is_text {
+            let token = parse_text(input)?;
+            text.get_or_insert_default().push_str(&token);
+        } else {
+            if let Some(text) = text.take() {
+                children.push(Node::Text(text));
+            }
+            if peek_closing_tag(input) {
+                if let Some(closing) = recover(input, parse_closing_tag) {
+                    diagnostics::emit(syn::Error::new(
+                        closing.span(),
+                        format!("unexpected closing tag `</{closing}>`"),
+                    ));
+                } else {
+                    skip_until(input, peek_node);
+                }
+            } else if let Some(node) = recover(input, Node::parse) {
+                children.push(node);
+            } else {
+                skip_until(input, peek_node);
+            }
+        }
+        prev_end = Some(last_span(start, input.cursor()).end());
+        prev_is_text = is_text;
+    }
+
+    // Whitespace before the closing tag on the same line is kept as well.
+    if let Some(end) = prev_end
+        && !input.is_empty()
+        && let Gap::Inline(columns) = Gap::between(end, input.span().start())
+    {
+        text.get_or_insert_default().push_str(&" ".repeat(columns));
     }
+    if let Some(text) = text {
+        children.push(Node::Text(text));
+    }
+
+    Ok(children)
 }
 
 impl Parse for Node {
@@ -26,64 +211,421 @@ impl Parse for Node {
         if input.peek(Brace) {
             let content;
             braced!(content in input);
-            return Ok(Self::Dynamic(content.parse()?));
+            // A closure without parameters marks its body as reactive: `{move || ...}`.
+            if content.peek(Token![||]) || (content.peek(Token![move]) && content.peek2(Token![||]))
+            {
+                content.parse::<Option<Token![move]>>()?;
+                content.parse::<Token![||]>()?;
+                return Ok(Self::Reactive(Box::new(parse_block_content(&content)?)));
+            }
+            // `{static if ...}` and so on are rendered once, so they can move the values they use.
+            if content.parse::<Option<Token![static]>>()?.is_some() {
+                if !(content.peek(Token![if])
+                    || content.peek(Token![match])
+                    || content.peek(Token![for]))
+                {
+                    return Err(content.error("expected `if`, `match` or `for` after `static`"));
+                }
+                return parse_block_content(&content);
+            }
+            // Control flow is rendered again whenever the signals it reads change.
+            return Ok(match parse_block_content(&content)? {
+                node @ (Self::If(_) | Self::Match(_) | Self::For(_)) => {
+                    Self::Reactive(Box::new(node))
+                }
+                node => node,
+            });
         }
 
-        return Ok(Self::Text(input.parse::<TokenTree>()?.to_string()));
+        Ok(Self::Text(parse_text(input)?))
     }
 }
 
-fn is_closing_tag(input: ParseStream, name: &Ident) -> bool {
-    let fork = input.fork();
+/// Parse the content of a braced node: an `if`, `match` or `for` block, or an expression.
+fn parse_block_content(content: ParseStream) -> syn::Result<Node> {
+    if content.peek(Token![if]) {
+        return Ok(Node::If(content.parse()?));
+    }
+    if content.peek(Token![match]) {
+        return Ok(Node::Match(content.parse()?));
+    }
+    if content.peek(Token![for]) {
+        return Ok(Node::For(content.parse()?));
+    }
+    let expr = parse_expr(content)?;
+    // `{"{count} items"}` is shorthand for `format!("{count} items")`.
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = &expr
+        && let Some(text) = format_text(&lit.value())
+    {
+        return Ok(match text {
+            FormatText::Interpolated => Node::Dynamic(parse_quote! { ::std::format!(#lit) }),
+            FormatText::Escaped(text) => Node::Text(text),
+        });
+    }
+    Ok(Node::Dynamic(expr))
+}
 
-    fork.parse::<Token![<]>().is_ok()
-        && fork.parse::<Token![/]>().is_ok()
-        && fork.parse::<Ident>().map(|id| id == *name).unwrap_or(false)
+/// A string literal that is a valid format string, see [`format_text`].
+enum FormatText {
+    /// The literal interpolates variables, e.g. `"{count} items"`.
+    Interpolated,
+    /// The literal only escapes braces, e.g. `"{{"`, and is rendered as this text.
+    Escaped(String),
 }
+
+/// Returns how to render `value` if it is a format string with at least one `{{`, `}}` or
+/// `{<ident>}` (optionally with a format spec, e.g. `{price:.2}`). Other literals, e.g. `"{"` or
+/// `"{0}"`, are rendered as they are.
+fn format_text(value: &str) -> Option<FormatText> {
+    let mut text = String::new();
+    let mut interpolated = false;
+    let mut escaped = false;
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                text.push('{');
+                escaped = true;
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                text.push('}');
+                escaped = true;
+            }
+            '{' => {
+                let (placeholder, rest) = chars.as_str().split_once('}')?;
+                let name = placeholder
+                    .split_once(':')
+                    .map_or(placeholder, |(name, _)| name);
+                syn::parse_str::<Ident>(name).ok()?;
+                chars = rest.chars();
+                interpolated = true;
+            }
+            '}' => return None,
+            c => text.push(c),
+        }
+    }
+    match (interpolated, escaped) {
+        (true, _) => Some(FormatText::Interpolated),
+        (false, true) => Some(FormatText::Escaped(text)),
+        (false, false) => None,
+    }
+}
+
+/// Parse a braced block of markup: `{ <markup> }`.
+fn parse_block(input: ParseStream) -> syn::Result<Root> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut branches = Vec::new();
+        let mut else_branch = None;
+
+        input.parse::<Token![if]>()?;
+        loop {
+            let condition = Expr::parse_without_eager_brace(input)?;
+            branches.push((condition, parse_block(input)?));
+
+            if input.parse::<Option<Token![else]>>()?.is_none() {
+                break;
+            }
+            if input.parse::<Option<Token![if]>>()?.is_none() {
+                else_branch = Some(parse_block(input)?);
+                break;
+            }
+        }
+
+        Ok(Self {
+            branches,
+            else_branch,
+        })
+    }
+}
+
+impl Parse for MatchNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![match]>()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+        braced!(content in input);
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            let pat = Pat::parse_multi_with_leading_vert(&content)?;
+            let guard = match content.parse::<Option<Token![if]>>()? {
+                Some(_) => Some(content.parse()?),
+                None => None,
+            };
+            content.parse::<Token![=>]>()?;
+            // An arm is either a block of markup or a single node, e.g. `<li/>`.
+            let body = if content.peek(Brace) {
+                parse_block(&content)?
+            } else {
+                Root(vec![content.parse()?])
+            };
+            content.parse::<Option<Token![,]>>()?;
+            arms.push(MatchArm { pat, guard, body });
+        }
+
+        Ok(Self { expr, arms })
+    }
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_single(input)?;
+        input.parse::<Token![in]>()?;
+        let iter = Expr::parse_without_eager_brace(input)?;
+        let body = parse_block(input)?;
+        Ok(Self { pat, iter, body })
+    }
+}
+
+/// Returns `true` if the next tokens are a closing tag: `</`.
+fn peek_closing_tag(input: ParseStream) -> bool {
+    input.peek(Token![<]) && input.peek2(Token![/])
+}
+
+/// Parse a closing tag, returning its name.
+fn parse_closing_tag(input: ParseStream) -> syn::Result<Ident> {
+    input.parse::<Token![<]>()?;
+    input.parse::<Token![/]>()?;
+    let name = Ident::parse_any(input)?;
+    input.parse::<Token![>]>()?;
+    Ok(name)
+}
+
 impl Parse for TagNode {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         input.parse::<Token![<]>()?;
-        let name = input.parse::<Ident>()?;
+        // Tag names may be Rust keywords, e.g. SVG's `<use>`.
+        let name = Ident::parse_any(input)?;
         let mut props = Vec::new();
 
-        while !input.is_empty() && !input.peek(Token![>]) {
-            props.push(input.parse()?);
-        }
+        let mut spreads = Vec::new();
 
-        input.parse::<Token![>]>()?;
+        while !input.is_empty() && !input.peek(Token![>]) && !input.peek(Token![/]) {
+            let parsed = if input.peek(Brace) {
+                recover(input, parse_spread).map(|spread| spreads.push(spread))
+            } else {
+                recover(input, Prop::parse).map(|prop| props.push(prop))
+            };
+            if parsed.is_none() {
+                skip_until(input, |input| {
+                    input.peek(Ident::peek_any)
+                        || input.peek(Brace)
+                        || input.peek(Token![>])
+                        || input.peek(Token![/])
+                });
+            }
+        }
 
-        let mut children = Vec::new();
-        // While we don't reach the closing tag
-        while !is_closing_tag(input, &name) {
-            children.push(input.parse()?);
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("unclosed tag `<{name}`, expected `>`"),
+            ));
         }
 
-        input.parse::<Token![<]>()?;
-        input.parse::<Token![/]>()?;
-        let span = input.fork();
-        let closing_name = input.parse::<Ident>()?;
-        input.parse::<Token![>]>()?;
-        if name != closing_name {
-            return Err(span.error(format!(
-                "mismatched closing tag. Expected: {name}, got {closing_name}"
-            )));
+        // Self-closing tag: `<name ... />`.
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                name,
+                props,
+                spreads,
+                children: Vec::new(),
+                scopes: Vec::new(),
+            });
         }
 
-        Ok(Self {
+        let open = input.parse::<Token![>]>()?;
+
+        // Parse children until we reach a closing tag, which may not be ours.
+        OPEN_TAGS.with(|tags| tags.borrow_mut().push(name.to_string()));
+        let children = parse_children(input, Some(open.span), |input| {
+            input.is_empty() || peek_closing_tag(input)
+        });
+        OPEN_TAGS.with(|tags| tags.borrow_mut().pop());
+
+        let tag = Self {
             name,
             props,
-            children,
-        })
+            spreads,
+            children: children?,
+            scopes: Vec::new(),
+        };
+        let name = &tag.name;
+
+        if !tag.children.is_empty()
+            && let Some(prop) = tag.props.iter().find(|prop| prop.identifier == INNER_HTML)
+        {
+            diagnostics::emit(syn::Error::new(
+                prop.span,
+                format!("`<{name}>` cannot have both `{INNER_HTML}` and children"),
+            ));
+        }
+
+        if let Some(prop) = tag.props.iter().find(|prop| {
+            prop.identifier == "css" && !matches!(prop.prop_type, PropType::Expression { .. })
+        }) {
+            diagnostics::emit(syn::Error::new(
+                prop.span,
+                "expected a style created with `css!`, e.g. `css={STYLE}`",
+            ));
+        }
+
+        if input.is_empty() {
+            diagnostics::emit(syn::Error::new(
+                name.span(),
+                format!("unclosed tag `<{name}>`"),
+            ));
+            return Ok(tag);
+        }
+
+        let fork = input.fork();
+        let closing = parse_closing_tag(&fork)?;
+        if closing == *name {
+            input.advance_to(&fork);
+            return Ok(tag);
+        }
+
+        let mut error = syn::Error::new(name.span(), format!("unclosed tag `<{name}>`"));
+        if OPEN_TAGS.with(|tags| tags.borrow().iter().any(|tag| closing == tag)) {
+            // Leave the closing tag to the enclosing tag it belongs to.
+            error.combine(syn::Error::new(
+                closing.span(),
+                format!("note: `</{closing}>` closes the enclosing `<{closing}>` first"),
+            ));
+        } else {
+            // Most likely a typo, so treat it as the closing tag of this one.
+            error.combine(syn::Error::new(
+                closing.span(),
+                format!("note: the closing tag `</{closing}>` does not match `<{name}>`"),
+            ));
+            input.advance_to(&fork);
+        }
+        diagnostics::emit(error);
+
+        Ok(tag)
     }
 }
 
+/// Parse spread attributes: `{..<expr>}`.
+fn parse_spread(input: ParseStream) -> syn::Result<Expr> {
+    let content;
+    braced!(content in input);
+    if content.parse::<Option<Token![..]>>()?.is_none() {
+        return Err(content.error("expected `..` to spread attributes, e.g. `{..attrs}`"));
+    }
+    parse_expr(&content)
+}
+
+/// Modifiers accepted after an event name, e.g. `on:click|once`.
+const EVENT_MODIFIERS: &[&str] = &["capture", "passive", "once", "prevent", "stop"];
+
+/// Parse an attribute name, which may contain hyphens (`data-id`) and be a Rust keyword
+/// (`type`). Returns the name and the span of its last identifier.
+fn parse_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let ident = Ident::parse_any(input)?;
+    let mut name = ident.to_string();
+    let mut span = ident.span();
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        let ident = Ident::parse_any(input)?;
+        name = format!("{name}-{ident}");
+        span = ident.span();
+    }
+    Ok((name, span))
+}
+
 impl Parse for Prop {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let identifier = input.parse::<Ident>()?.to_string();
+        let (mut identifier, mut span) = parse_name(input)?;
+
+        // Directive syntax: `<namespace>:<name>`, e.g. `on:click` or `xlink:href`.
+        if input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            let (name, name_span) = parse_name(input)?;
+            identifier = format!("{identifier}:{name}");
+            span = name_span;
+        }
+
+        // Event names are lowercase in the DOM, so `onClick` and `on_click` would never fire.
+        if let Some(event) = event_name(&identifier)
+            && event.contains(|c: char| c.is_ascii_uppercase() || c == '_' || c == '-')
+        {
+            let prefix = &identifier[..identifier.len() - event.len()];
+            let event = event.replace(['_', '-'], "").to_ascii_lowercase();
+            let suggestion = format!("{prefix}{event}");
+            diagnostics::emit(syn::Error::new(
+                span,
+                format!("unknown event `{identifier}`, did you mean `{suggestion}`?"),
+            ));
+            identifier = suggestion;
+        } else if let Some(event) = event_name(&identifier)
+            && let Some(suggestion) = events::suggest(event)
+        {
+            let suggestion = format!(
+                "{}{suggestion}",
+                &identifier[..identifier.len() - event.len()]
+            );
+            diagnostics::emit(syn::Error::new(
+                span,
+                format!("unknown event `{identifier}`, did you mean `{suggestion}`?"),
+            ));
+            identifier = suggestion;
+        }
+
+        let mut modifiers = Vec::new();
+        while input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            let modifier = input.parse::<Ident>()?;
+            let name = modifier.to_string();
+            if event_name(&identifier).is_none() {
+                diagnostics::emit(syn::Error::new(
+                    modifier.span(),
+                    "modifiers are only allowed on event listeners",
+                ));
+            } else if !EVENT_MODIFIERS.contains(&name.as_str()) {
+                let message = match diagnostics::suggest(&name, EVENT_MODIFIERS) {
+                    Some(suggestion) => {
+                        format!("unknown event modifier `{name}`, did you mean `{suggestion}`?")
+                    }
+                    None => format!(
+                        "unknown event modifier `{name}`. Expected one of: {}",
+                        EVENT_MODIFIERS.join(", ")
+                    ),
+                };
+                diagnostics::emit(syn::Error::new(modifier.span(), message));
+            } else {
+                modifiers.push(modifier);
+            }
+        }
+        // Browsers ignore `preventDefault` in passive listeners.
+        if modifiers.iter().any(|modifier| modifier == "passive")
+            && let Some(prevent) = modifiers.iter().find(|modifier| *modifier == "prevent")
+        {
+            diagnostics::emit(syn::Error::new(
+                prevent.span(),
+                "`prevent` cannot be combined with `passive`, which ignores `preventDefault`",
+            ));
+        }
+
         let prop_type = input.parse::<PropType>()?;
 
         Ok(Self {
             identifier,
+            span,
+            modifiers,
             prop_type,
         })
     }
@@ -91,7 +633,7 @@ impl Parse for Prop {
 
 impl Parse for PropType {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if !input.parse::<Token![=]>().is_ok() {
+        if input.parse::<Token![=]>().is_err() {
             return Ok(Self::Boolean);
         };
 
@@ -103,10 +645,10 @@ impl Parse for PropType {
             let content;
             braced!(content in input);
             return Ok(Self::Expression {
-                value: content.parse()?,
+                value: parse_expr(&content)?,
             });
         };
 
-        Err(input.error("unknown token"))
+        Err(input.error("expected a string literal or a braced expression, e.g. `{value}`"))
     }
 }
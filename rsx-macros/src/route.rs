@@ -0,0 +1,166 @@
+//! `#[derive(Route)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use rsx_route_pattern::RoutePattern;
+use syn::{Data, DeriveInput, Fields, LitStr, Variant, spanned::Spanned};
+
+use crate::diagnostics;
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Route` can only be derived for enums",
+        ));
+    };
+
+    let mut routes = Vec::new();
+    let mut not_found = None;
+    for variant in &data.variants {
+        if variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("not_found"))
+        {
+            if !matches!(variant.fields, Fields::Unit) {
+                diagnostics::emit(syn::Error::new_spanned(
+                    &variant.fields,
+                    "the `#[not_found]` route cannot have fields",
+                ));
+            } else if not_found.is_some() {
+                diagnostics::emit(syn::Error::new_spanned(
+                    &variant.ident,
+                    "there can only be one `#[not_found]` route",
+                ));
+            } else {
+                not_found = Some(&variant.ident);
+            }
+            continue;
+        }
+
+        let Some(attr) = variant.attrs.iter().find(|attr| attr.path().is_ident("to")) else {
+            diagnostics::emit(syn::Error::new_spanned(
+                &variant.ident,
+                "missing route pattern, e.g. `#[to(\"/\")]`, or `#[not_found]`",
+            ));
+            continue;
+        };
+        match attr.parse_args::<LitStr>() {
+            Ok(pattern) => routes.push(route(variant, &pattern)),
+            Err(error) => diagnostics::emit(error),
+        }
+    }
+
+    let Some(not_found) = not_found else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing a `#[not_found]` route for paths that match no other route",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rsx_web::router::Route for #name #ty_generics #where_clause {
+            fn match_path(path: &str) -> Self {
+                #(#routes)*
+                Self::#not_found
+            }
+        }
+    })
+}
+
+/// Generate the block returning `variant` if `pattern` matches the path and every parameter
+/// parses into its field.
+fn route(variant: &Variant, pattern: &LitStr) -> TokenStream {
+    // The generated code parses the pattern again at runtime, with the same parser.
+    let params = match RoutePattern::parse(&pattern.value()) {
+        Ok(parsed) => parsed.params().map(str::to_string).collect::<Vec<_>>(),
+        Err(error) => {
+            diagnostics::emit(syn::Error::new(pattern.span(), error));
+            return TokenStream::new();
+        }
+    };
+
+    let ident = &variant.ident;
+    let (bindings, construct) = match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().flat_map(|field| &field.ident);
+            for name in names.clone() {
+                if !params.iter().any(|param| name == param) {
+                    diagnostics::emit(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "field `{name}` has no matching parameter in `{}`",
+                            pattern.value()
+                        ),
+                    ));
+                }
+            }
+            let mut bindings = Vec::new();
+            for param in &params {
+                match names.clone().find(|name| *name == param) {
+                    Some(name) => bindings.push((param.clone(), name.clone())),
+                    None => diagnostics::emit(syn::Error::new(
+                        pattern.span(),
+                        format!("parameter `{param}` has no matching field in `{ident}`"),
+                    )),
+                }
+            }
+            let names = names.collect::<Vec<_>>();
+            (bindings, quote!({ #(#names),* }))
+        }
+        Fields::Unnamed(fields) => {
+            if fields.unnamed.len() != params.len() {
+                diagnostics::emit(syn::Error::new(
+                    fields.span(),
+                    format!(
+                        "`{ident}` has {} fields but `{}` has {} parameters",
+                        fields.unnamed.len(),
+                        pattern.value(),
+                        params.len()
+                    ),
+                ));
+            }
+            let bindings = params
+                .iter()
+                .enumerate()
+                .map(|(i, param)| (param.clone(), format_ident!("field{i}")))
+                .collect::<Vec<_>>();
+            let names = bindings.iter().map(|(_, binding)| binding);
+            (bindings.clone(), quote!((#(#names),*)))
+        }
+        Fields::Unit => {
+            if let Some(param) = params.first() {
+                diagnostics::emit(syn::Error::new(
+                    pattern.span(),
+                    format!("parameter `{param}` has no matching field in `{ident}`"),
+                ));
+            }
+            (Vec::new(), TokenStream::new())
+        }
+    };
+
+    let bindings = bindings.iter().map(|(param, binding)| {
+        quote! {
+            let ::std::option::Option::Some(#binding) = params
+                .get(#param)
+                .and_then(|value| ::std::str::FromStr::from_str(value).ok())
+            else {
+                break 'route;
+            };
+        }
+    });
+    quote! {
+        'route: {
+            static PATTERN: ::std::sync::LazyLock<::rsx_web::router::RoutePattern> =
+                ::std::sync::LazyLock::new(|| ::rsx_web::router::RoutePattern::parse(#pattern).unwrap());
+            let ::std::option::Option::Some(params) = PATTERN.matches(path) else {
+                break 'route;
+            };
+            #(#bindings)*
+            return Self::#ident #construct;
+        }
+    }
+}
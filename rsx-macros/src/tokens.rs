@@ -1,12 +1,19 @@
+use proc_macro2::{Ident, Span};
 use quote::{ToTokens, quote};
 
-use crate::ir::{Node, Root, TagNode};
+use crate::ir::{
+    ForNode, INNER_HTML, IfNode, MatchArm, MatchNode, Node, Root, TagNode, event_name,
+};
 
+/// A list of nodes is a single `rsx::Element`, wrapped in a fragment unless there is exactly one.
 impl ToTokens for Root {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        for child in &self.0 {
-            ToTokens::to_tokens(child, tokens);
-        }
+        tokens.extend(match self.0.as_slice() {
+            [node] => quote! { rsx::Element::from(#node) },
+            nodes => quote! {
+                rsx::Element::Fragment(::std::vec![#(rsx::Element::from(#nodes)),*])
+            },
+        });
     }
 }
 
@@ -15,43 +22,169 @@ impl ToTokens for Node {
         match self {
             Node::Tag(tag_node) => ToTokens::to_tokens(tag_node, tokens),
             Node::Text(lit_str) => ToTokens::to_tokens(lit_str, tokens),
-            Node::Dynamic(expr) => ToTokens::to_tokens(expr, tokens),
+            Node::Dynamic(expr) => tokens.extend(quote! {{
+                #[allow(unused_imports)]
+                use rsx::__private::{ViaDisplay as _, ViaInto as _};
+                (&rsx::__private::Child::new(#expr)).to_element()
+            }}),
+            Node::If(if_node) => ToTokens::to_tokens(if_node, tokens),
+            Node::Match(match_node) => ToTokens::to_tokens(match_node, tokens),
+            Node::For(for_node) => ToTokens::to_tokens(for_node, tokens),
+            Node::Reactive(node) => tokens.extend(quote! {
+                rsx::Element::Dynamic(::std::boxed::Box::new(move || rsx::Element::from(#node)))
+            }),
         }
     }
 }
 
+impl ToTokens for IfNode {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let conditions = self.branches.iter().map(|(condition, _)| condition);
+        let branches = self.branches.iter().map(|(_, branch)| branch);
+        let else_branch = match &self.else_branch {
+            Some(else_branch) => quote! { #else_branch },
+            None => quote! { rsx::Element::Fragment(::std::vec::Vec::new()) },
+        };
+        tokens.extend(quote! {
+            #(if #conditions { #branches } else)* { #else_branch }
+        });
+    }
+}
+
+impl ToTokens for MatchNode {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let expr = &self.expr;
+        let arms = self.arms.iter().map(|MatchArm { pat, guard, body }| {
+            let guard = guard.as_ref().map(|guard| quote! { if #guard });
+            quote! { #pat #guard => #body, }
+        });
+        tokens.extend(quote! {
+            match #expr { #(#arms)* }
+        });
+    }
+}
+
+impl ToTokens for ForNode {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ForNode { pat, iter, body } = self;
+        tokens.extend(quote! {
+            rsx::Element::Fragment(
+                ::std::iter::IntoIterator::into_iter(#iter).map(|#pat| #body).collect()
+            )
+        });
+    }
+}
+
 impl ToTokens for TagNode {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let name = self.name.to_string();
         let mut attributes = Vec::new();
         let mut event_listeners = Vec::new();
+        let mut classes = Vec::new();
+        let mut styles = Vec::new();
+        let mut properties = Vec::new();
+        // `bind:` directives, bound before the element is built so that their property and
+        // listeners can be added to it, and the styles of `css` props.
+        let mut bindings = Vec::new();
+        let mut inner_html = quote! { ::std::option::Option::None };
+        let mut node_ref = quote! { ::std::option::Option::None };
         for prop in &self.props {
+            let identifier = &prop.identifier;
             let value = match &prop.prop_type {
                 crate::ir::PropType::Plain { value } => quote! { #value },
                 crate::ir::PropType::Expression { value } => quote! { #value },
-                crate::ir::PropType::Boolean => quote! { "" },
+                // `disabled` or `class:active` on their own are always set.
+                crate::ir::PropType::Boolean => quote! { true },
             };
-            let identifier = &prop.identifier;
 
-            if let Some(identifier) = identifier.strip_prefix("on") {
-                event_listeners.push(
-                    quote! {(String::from(#identifier), Box::new(#value) as rsx::EventListener)},
-                );
+            if identifier == "css" {
+                // The scope class itself is added below, along with the inherited ones.
+                bindings.push(quote! { rsx::css::attach(#value); });
+            } else if identifier == INNER_HTML {
+                inner_html = quote! { ::std::option::Option::Some(rsx::MaybeDyn::from(#value)) };
+            } else if identifier == "ref" {
+                node_ref = quote! { ::std::option::Option::Some(#value) };
+            } else if let Some(class) = identifier.strip_prefix("class:") {
+                classes.push(quote! {(String::from(#class), rsx::MaybeDyn::from(#value))});
+            } else if let Some(property) = identifier.strip_prefix("style:") {
+                styles.push(quote! {(String::from(#property), rsx::MaybeDyn::from(#value))});
+            } else if let Some(property) = identifier.strip_prefix("prop:") {
+                properties.push(quote! {(String::from(#property), rsx::MaybeDyn::from(#value))});
+            } else if let Some(property) = identifier.strip_prefix("bind:") {
+                // Resolving the binding by path makes unknown properties a compile error.
+                let function = Ident::new(property, prop.span);
+                let bound = Ident::new(&format!("bound{}", bindings.len()), Span::mixed_site());
+                bindings.push(quote! { let #bound = rsx::bind::#function(#value); });
+                properties.push(quote! { #bound.property });
+                event_listeners.push(quote! { #bound.listeners });
+            } else if let Some(event) = event_name(identifier) {
+                // Resolving the descriptor by path makes unknown events a compile error.
+                let descriptor = Ident::new(event, prop.span);
+                let mut listener = quote! {
+                    rsx::events::listener(rsx::events::#descriptor, #value)
+                };
+                if !prop.modifiers.is_empty() {
+                    let options = prop.modifiers.iter().map(|modifier| {
+                        let field = match modifier.to_string().as_str() {
+                            "prevent" => Ident::new("prevent_default", modifier.span()),
+                            "stop" => Ident::new("stop_propagation", modifier.span()),
+                            _ => modifier.clone(),
+                        };
+                        quote! { #field: true }
+                    });
+                    listener = quote! {
+                        #listener.with_options(rsx::events::EventOptions {
+                            #(#options,)*
+                            ..::std::default::Default::default()
+                        })
+                    };
+                }
+                event_listeners.push(quote! {::std::vec![(String::from(#event), #listener)]});
             } else {
-                attributes.push(quote! {(String::from(#identifier),String::from(#value))});
+                attributes.push(quote! {(String::from(#identifier), rsx::MaybeDyn::from(#value))});
             }
         }
 
+        classes.extend(self.scopes.iter().map(|scope| {
+            quote! {(String::from(rsx::css::ScopedStyle::class(#scope)), rsx::MaybeDyn::from(true))}
+        }));
+
         let children = self
             .children
             .iter()
             .map(|child| quote! {rsx::Element::from(#child)});
 
-        tokens.extend(quote! { rsx::HTMLElement{
+        let element = quote! { rsx::HTMLElement{
             name: String::from(#name),
             attributes: ::std::collections::HashMap::from_iter(::std::vec![#(#attributes),*]),
-            event_listeners: ::std::collections::HashMap::from_iter(::std::vec![#(#event_listeners),*]),
+            event_listeners: rsx::events::listeners(
+                ::std::iter::empty()#(.chain(#event_listeners))*
+            ),
+            classes: ::std::collections::HashMap::from_iter(::std::vec![#(#classes),*]),
+            styles: ::std::collections::HashMap::from_iter(::std::vec![#(#styles),*]),
+            properties: ::std::collections::HashMap::from_iter(::std::vec![#(#properties),*]),
+            inner_html: #inner_html,
+            node_ref: #node_ref,
             children: ::std::vec![#(#children),*]
-        } });
+        } };
+
+        if self.spreads.is_empty() && bindings.is_empty() {
+            tokens.extend(element);
+        } else if self.spreads.is_empty() {
+            tokens.extend(quote! {{
+                #(#bindings)*
+                #element
+            }});
+        } else {
+            let spreads = &self.spreads;
+            // Hygienic, so that spread expressions can mention a variable named `element`.
+            let built = Ident::new("element", Span::mixed_site());
+            tokens.extend(quote! {{
+                #(#bindings)*
+                let mut #built = #element;
+                #(#built.spread(#spreads);)*
+                #built
+            }});
+        }
     }
 }
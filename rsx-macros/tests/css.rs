@@ -0,0 +1,62 @@
+use rsx::{css, css::ScopedStyle, rsx};
+use rsx_reactive::{root::create_root, signals::create_signal};
+
+const CARD: ScopedStyle = css!(
+    r#"
+    /* A card with a title. */
+    .card { padding: 1rem; border : 1px solid; }
+    .card > h2:hover, a[href^="http:"]::after { color: red }
+    :global(body.dark) p ~ * { color : white ; }
+    @media (max-width: 600px) {
+        .card { padding: 0 }
+    }
+    @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+    "#
+);
+
+#[test]
+fn scope_selectors() {
+    let class = CARD.class();
+    assert!(class.starts_with("rsx-"));
+    assert_eq!(
+        CARD.css(),
+        [
+            format!(".card.{class}{{padding:1rem;border:1px solid}}"),
+            format!(
+                ".card.{class} > h2.{class}:hover,a[href^=\"http:\"].{class}::after{{color:red}}"
+            ),
+            format!("body.dark p.{class} ~ .{class}{{color:white}}"),
+            format!("@media (max-width: 600px){{.card.{class}{{padding:0}}}}"),
+            "@keyframes fade{from { opacity: 0 } to { opacity: 1 }}".to_string(),
+        ]
+        .concat()
+    );
+    assert_eq!(
+        css!(".card { padding: 1rem }").class(),
+        css!(".card { padding: 1rem }").class()
+    );
+    assert_ne!(
+        css!(".card { padding: 1rem }").class(),
+        css!(".card { padding: 2rem }").class()
+    );
+}
+
+#[test]
+fn add_scope_class_to_descendants() {
+    let _ = create_root(|| {
+        let visible = create_signal(true);
+        let element = rsx!(
+            <ul class="list" css={CARD}>
+                <li class:active={true}>First</li>
+                {if visible.get() { <li>Second</li> }}
+            </ul>
+        );
+        let class = CARD.class();
+        assert_eq!(
+            element.to_string(),
+            format!(
+                r#"<ul class="list {class}"><li class="active {class}">First</li><li class="{class}">Second</li></ul>"#
+            )
+        );
+    });
+}
@@ -0,0 +1,129 @@
+use std::{cell::RefCell, future::poll_fn, rc::Rc, task::Poll};
+
+use rsx::form::{Form, create_form, create_form_with};
+use rsx_reactive::root::create_root;
+
+#[derive(Clone, Debug, Default, PartialEq, Form)]
+struct Signup {
+    name: String,
+    age: u32,
+    accept: bool,
+}
+
+#[test]
+fn fields_track_dirty_and_touched() {
+    let _ = create_root(|| {
+        let form = create_form_with(Signup {
+            name: "Ada".to_string(),
+            ..Default::default()
+        });
+        assert!(!form.dirty().get());
+
+        form.fields.name.value.set("Grace".to_string());
+        assert!(form.fields.name.dirty().get());
+        assert!(form.dirty().get());
+        assert!(!form.touched().get());
+
+        form.fields.age.touch();
+        assert!(form.touched().get());
+
+        form.reset();
+        assert_eq!(form.values().name, "Ada");
+        assert!(!form.dirty().get());
+        assert!(!form.touched().get());
+    });
+}
+
+#[test]
+fn validators_block_submit() {
+    let _ = create_root(|| {
+        let form = create_form::<Signup>();
+        form.fields.name.validate(|name| match name.is_empty() {
+            true => Err("required".to_string()),
+            false => Ok(()),
+        });
+        form.fields.accept.validate(|accept| match accept {
+            true => Ok(()),
+            false => Err("must be accepted".to_string()),
+        });
+        assert_eq!(
+            form.errors().get_clone(),
+            [
+                ("name", "required".to_string()),
+                ("accept", "must be accepted".to_string())
+            ]
+        );
+
+        let mut submitted = None;
+        assert!(!form.submit(|values| submitted = Some(values)));
+        assert_eq!(submitted, None);
+        // Submitting touches every field so that their errors are shown.
+        assert!(form.fields.name.touched().get());
+
+        form.fields.name.value.set("Ada".to_string());
+        form.fields.age.value.set(36);
+        form.fields.accept.value.set(true);
+        assert!(form.is_valid().get());
+        assert!(form.submit(|values| submitted = Some(values)));
+        assert_eq!(
+            submitted,
+            Some(Signup {
+                name: "Ada".to_string(),
+                age: 36,
+                accept: true
+            })
+        );
+    });
+}
+
+#[test]
+fn async_validators() {
+    let _ = create_root(|| {
+        let form = create_form::<Signup>();
+        form.fields.name.validate_async(|name| async move {
+            match name.as_str() {
+                // Never completes, like a slow server.
+                "slow" => std::future::pending().await,
+                "taken" => Err("name is taken".to_string()),
+                _ => Ok(()),
+            }
+        });
+        assert!(form.is_valid().get());
+
+        form.fields.name.value.set("slow".to_string());
+        assert!(form.fields.name.pending().get());
+        assert!(!form.is_valid().get());
+
+        form.fields.name.value.set("taken".to_string());
+        assert!(!form.fields.name.pending().get());
+        assert_eq!(form.fields.name.errors().get_clone(), ["name is taken"]);
+
+        form.fields.name.value.set("free".to_string());
+        assert!(form.is_valid().get());
+    });
+}
+
+#[test]
+fn submit_async_outlives_form() {
+    // The waker of the submit handler, woken to complete it.
+    let waker = Rc::new(RefCell::new(None));
+    let root = create_root({
+        let waker = waker.clone();
+        move || {
+            let form = create_form::<Signup>();
+            form.submit_async(move |_| {
+                let mut polled = false;
+                poll_fn(move |cx| match std::mem::replace(&mut polled, true) {
+                    true => Poll::Ready(()),
+                    false => {
+                        *waker.borrow_mut() = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                })
+            });
+            assert!(form.is_submitting().get());
+        }
+    });
+    root.dispose();
+    waker.borrow_mut().take().unwrap().wake();
+}
@@ -1,6 +1,312 @@
 use rsx::rsx;
+use rsx_reactive::{root::create_root, signals::create_signal};
 
 #[test]
 fn should_compile() {
     let _ = rsx!(<h1 prop="">Hello</h1>);
 }
+
+#[test]
+fn typed_event_handlers() {
+    let mut clicks = Vec::new();
+    let _ = rsx!(
+        <form on:submit={|event| event.prevent_default()}>
+            <input oninput={|event| drop(event.data())}></input>
+            <button onclick={move |event| clicks.push(event.client_x())}>Submit</button>
+        </form>
+    );
+}
+
+#[test]
+fn event_modifiers() {
+    let element = rsx!(
+        <div on:scroll|passive={|_| ()}>
+            <form on:submit|prevent|stop={|_| ()}></form>
+        </div>
+    );
+    let rsx::Element::HTMLElement(div) = element else {
+        panic!("expected an element");
+    };
+    let scroll = &div.event_listeners["scroll"].options;
+    assert!(scroll.passive && !scroll.once);
+
+    let rsx::Element::HTMLElement(form) = &div.children[0] else {
+        panic!("expected an element");
+    };
+    let submit = &form.event_listeners["submit"].options;
+    assert!(submit.prevent_default && submit.stop_propagation && !submit.capture);
+}
+
+#[test]
+fn control_flow() {
+    let logged_in = true;
+    let items = ["a", "b", "c"];
+    let status: Option<u32> = None;
+    let element = rsx!(
+        <main>
+            {if logged_in { <p>Welcome</p> } else { <button>Log in</button> }}
+            {if let Some(code) = status { <p>{code}</p> }}
+            {match status {
+                Some(code) if code >= 400 => <p>Error</p>,
+                Some(_) => { <p>Ok</p> }
+                None => <p/>,
+            }}
+            <ul>
+                {for item in items.iter() { <li>{item}</li> }}
+            </ul>
+        </main>
+    );
+
+    assert_eq!(
+        element.to_string(),
+        "<main><p>Welcome</p><p></p><ul><li>a</li><li>b</li><li>c</li></ul></main>"
+    );
+}
+
+#[test]
+fn reactive_control_flow() {
+    let _ = create_root(|| {
+        let flag = create_signal(true);
+        let name = String::from("x");
+        let element = rsx!(<div>{if flag.get() { <p>{name.clone()}</p> }}</div>);
+        let rsx::Element::HTMLElement(div) = &element else {
+            panic!("expected an element");
+        };
+        assert!(matches!(div.children[0], rsx::Element::Dynamic(_)));
+        assert_eq!(element.to_string(), "<div><p>x</p></div>");
+
+        flag.set(false);
+        assert_eq!(element.to_string(), "<div></div>");
+    });
+}
+
+#[test]
+fn static_blocks_move_owned_captures() {
+    let name = String::from("x");
+    let element = rsx!(<div>{static if name.is_empty() { <p/> } else { <p>{name}</p> }}</div>);
+    assert_eq!(element.to_string(), "<div><p>x</p></div>");
+}
+
+#[test]
+fn iterate_over_owned_collection() {
+    let items = vec!["a".to_string(), "b".to_string()];
+    let element = rsx!(<ul>{static for item in items { <li>{item}</li> }}</ul>);
+    assert_eq!(element.to_string(), "<ul><li>a</li><li>b</li></ul>");
+}
+
+#[test]
+fn nested_for_over_owned_collections() {
+    let rows = vec![
+        vec!["a".to_string(), "b".to_string()],
+        vec!["c".to_string()],
+    ];
+    let element = rsx!(
+        <table>
+            {static for row in rows { <tr>{static for cell in row { <td>{cell}</td> }}</tr> }}
+        </table>
+    );
+    assert_eq!(
+        element.to_string(),
+        "<table><tr><td>a</td><td>b</td></tr><tr><td>c</td></tr></table>"
+    );
+}
+
+#[test]
+fn reactive_blocks() {
+    let _ = create_root(|| {
+        let count = create_signal(1);
+        let rows = create_signal(vec![vec!["a".to_string()]]);
+        let element = rsx!(
+            <div>
+                {if count.get() > 1 { <p>many</p> } else { <p>one</p> }}
+                {move || count.get() * 10}
+                {for row in rows.get_clone() {
+                    <ul>{static for cell in row { <li>{cell}</li> }}</ul>
+                }}
+            </div>
+        );
+        let rsx::Element::HTMLElement(div) = &element else {
+            panic!("expected an element");
+        };
+        assert!(
+            div.children
+                .iter()
+                .all(|child| matches!(child, rsx::Element::Dynamic(_)))
+        );
+        assert_eq!(
+            element.to_string(),
+            "<div><p>one</p>10<ul><li>a</li></ul></div>"
+        );
+
+        count.set(2);
+        rows.set(vec![vec!["b".to_string(), "c".to_string()]]);
+        assert_eq!(
+            element.to_string(),
+            "<div><p>many</p>20<ul><li>b</li><li>c</li></ul></div>"
+        );
+    });
+}
+
+fn text_children(element: rsx::Element) -> Vec<String> {
+    let rsx::Element::HTMLElement(element) = element else {
+        panic!("expected an element");
+    };
+    element
+        .children
+        .into_iter()
+        .map(|child| match child {
+            rsx::Element::Text(text) => text,
+            _ => panic!("expected a text node"),
+        })
+        .collect()
+}
+
+#[test]
+fn borrowed_attribute_values() {
+    let name = String::from("Ada");
+    let count = 3;
+    let color = String::from("red");
+    let element = rsx!(<p title={&name} data-count={&count} style:color={&color}>Hi</p>);
+    assert_eq!(
+        element.to_string(),
+        r#"<p data-count="3" style="color: red" title="Ada">Hi</p>"#
+    );
+}
+
+#[test]
+fn text_spacing() {
+    let count = 3;
+    let element = rsx!(
+        <p>
+            Hello,   world! {count} items
+            on two lines.
+        </p>
+    );
+    assert_eq!(
+        text_children(element),
+        ["Hello,   world! ", "3", " items on two lines."]
+    );
+
+    let element = rsx!(<h1>This is a RSX snippet {1} </h1>);
+    assert_eq!(text_children(element), ["This is a RSX snippet ", "1", " "]);
+}
+
+#[test]
+fn string_literals_and_interpolation() {
+    let count = 3;
+    let price = 1.5;
+    let element = rsx!(
+        <p>"  padded, verbatim  " {"{count} items"} {"{price:.2}"} {"{"} {"{{count}}"} {"{0}"}</p>
+    );
+    assert_eq!(
+        text_children(element),
+        [
+            "  padded, verbatim   ",
+            "3 items",
+            " ",
+            "1.50",
+            " ",
+            "{",
+            " ",
+            "{count}",
+            " ",
+            "{0}"
+        ]
+    );
+}
+
+#[test]
+fn display_renders_html() {
+    let element = rsx!(<p id="x" class="a" onclick={|_| ()}>{"<b>"} & more<br/></p>);
+    assert_eq!(
+        element.to_string(),
+        r#"<p class="a" id="x">&lt;b&gt; &amp; more<br></p>"#
+    );
+}
+
+#[test]
+fn equality_ignores_listeners() {
+    let count = 1;
+    assert_eq!(
+        rsx!(<button onclick={|_| ()}>Clicked {count} times</button>),
+        rsx!(<button>Clicked {"1"} times</button>)
+    );
+    assert_ne!(rsx!(<p class="a"></p>), rsx!(<p class="b"></p>));
+}
+
+#[test]
+fn equality_does_not_call_dynamic_elements() {
+    let element = rsx!(<p>{rsx::Element::Dynamic(Box::new(|| panic!("called")))}</p>);
+    assert_ne!(element, rsx!(<p></p>));
+    assert_ne!(
+        rsx!(<p class={|| -> String { panic!("called") }}></p>),
+        rsx!(<p></p>)
+    );
+}
+
+#[test]
+fn compare_resolved_elements() {
+    let _ = create_root(|| {
+        let count = create_signal(1);
+        let element =
+            rsx!(<p class={move || format!("n{}", count.get())}>{move || count.get()}</p>);
+        assert_eq!(element.resolve(), rsx!(<p class="n1">1</p>));
+        count.set(2);
+        assert_eq!(element.resolve(), rsx!(<p class="n2">2</p>));
+    });
+}
+
+#[test]
+fn debug_shows_the_tree() {
+    let element = rsx!(<a href="/" on:click={|_| ()}>Home</a>);
+    assert_eq!(
+        format!("{element:?}"),
+        r#"HTMLElement { name: "a", attributes: {"href": String("/")}, classes: {}, styles: {}, properties: {}, event_listeners: ["click"], inner_html: None, children: [Text("Home")], .. }"#
+    );
+}
+
+#[test]
+fn display_values_by_value() {
+    struct Money(u32);
+
+    impl std::fmt::Display for Money {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "${}", self.0)
+        }
+    }
+
+    let element = rsx!(<p>{Money(5)}{rsx!(<b>!</b>)}</p>);
+    assert_eq!(element.to_string(), "<p>$5<b>!</b></p>");
+}
+
+#[test]
+fn debug_does_not_call_dynamic_elements() {
+    let element = rsx!(<p>{rsx::Element::Dynamic(Box::new(|| panic!("called")))}</p>);
+    assert!(format!("{element:?}").contains("children: [Dynamic(..)]"));
+}
+
+#[test]
+fn bind_and_prop_directives() {
+    let _ = create_root(|| {
+        let text = create_signal(String::from("Hello"));
+        let checked = create_signal(true);
+        let element = rsx!(
+            <div>
+                <input bind:value={text} on:input={|_| ()}></input>
+                <input type="checkbox" bind:checked={checked}></input>
+                <textarea bind:value={text}></textarea>
+                <input value="initial" prop:value="current"></input>
+            </div>
+        );
+        assert_eq!(
+            element.to_string(),
+            r#"<div><input value="Hello"><input checked type="checkbox"><textarea>Hello</textarea><input value="current"></div>"#
+        );
+        text.set(String::from("Bye"));
+        checked.set(false);
+        assert_eq!(
+            element.to_string(),
+            r#"<div><input value="Bye"><input type="checkbox"><textarea>Bye</textarea><input value="current"></div>"#
+        );
+    });
+}
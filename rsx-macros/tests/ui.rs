@@ -0,0 +1,5 @@
+/// Checks the messages and spans of compile errors, which `compile_fail` doctests cannot.
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
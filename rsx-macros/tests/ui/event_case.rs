@@ -0,0 +1,5 @@
+use rsx::rsx;
+
+fn main() {
+    let _ = rsx!(<button onClick={|_| ()}>Click me</button>);
+}
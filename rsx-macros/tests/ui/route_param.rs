@@ -0,0 +1,13 @@
+use rsx_web::router::Route;
+
+#[derive(Route)]
+enum AppRoute {
+    #[to("/users/<é>")]
+    User(String),
+    #[to("/types/<r#type>")]
+    Type(String),
+    #[not_found]
+    NotFound,
+}
+
+fn main() {}
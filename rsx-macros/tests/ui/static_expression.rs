@@ -0,0 +1,6 @@
+use rsx::rsx;
+
+fn main() {
+    let name = String::from("x");
+    let _ = rsx!(<p>{static name}</p>);
+}
@@ -0,0 +1,5 @@
+use rsx::rsx;
+
+fn main() {
+    let _ = rsx!(<div><p>Hello</div>);
+}
@@ -0,0 +1,6 @@
+use rsx::rsx;
+
+fn main() {
+    let _ = rsx!(<button onclik={|_| ()}>Click me</button>);
+    let _ = rsx!(<input on:imput={|_| ()}></input>);
+}
@@ -0,0 +1,6 @@
+use rsx::rsx;
+
+fn main() {
+    let _ = rsx!(<button on:click|onse={|_| ()}>Click me</button>);
+    let _ = rsx!(<button on:click|later={|_| ()}>Click me</button>);
+}
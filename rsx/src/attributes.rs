@@ -0,0 +1,235 @@
+//! Attribute values and collections of attributes that can be spread onto an element.
+
+use std::collections::{HashMap, hash_map::Entry};
+
+use rsx_reactive::signals::{ReadSignal, Signal};
+
+use crate::{
+    EventListener, HTMLElement, MaybeDyn,
+    events::{self, EventDescriptor, EventHandlers},
+};
+
+/// The value of an HTML attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeValue {
+    /// A regular attribute, e.g. `title="..."`.
+    String(String),
+    /// A boolean attribute, e.g. `disabled`. It is only set when `true`.
+    Bool(bool),
+}
+
+impl AttributeValue {
+    /// Returns the value as a string, or `None` if this is a boolean attribute.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            Self::Bool(_) => None,
+        }
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<&String> for AttributeValue {
+    fn from(value: &String) -> Self {
+        Self::String(value.clone())
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+macro_rules! impl_from_static {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for MaybeDyn<AttributeValue> {
+                fn from(value: $ty) -> Self {
+                    Self::Static(value.into())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for AttributeValue {
+                fn from(value: $ty) -> Self {
+                    Self::String(value.to_string())
+                }
+            }
+
+            impl From<&$ty> for AttributeValue {
+                fn from(value: &$ty) -> Self {
+                    Self::String(value.to_string())
+                }
+            }
+        )*
+        impl_from_static!($($ty, &$ty),*);
+    };
+}
+
+impl_from_static!(AttributeValue, String, &str, &String, bool);
+impl_from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl<F: Fn() -> U + 'static, U: Into<AttributeValue>> From<F> for MaybeDyn<AttributeValue> {
+    fn from(f: F) -> Self {
+        Self::Dynamic(Box::new(move || f().into()))
+    }
+}
+
+impl<T: Clone + Into<AttributeValue>> From<ReadSignal<T>> for MaybeDyn<AttributeValue> {
+    fn from(signal: ReadSignal<T>) -> Self {
+        Self::Dynamic(Box::new(move || signal.get_clone().into()))
+    }
+}
+
+impl<T: Clone + Into<AttributeValue>> From<Signal<T>> for MaybeDyn<AttributeValue> {
+    fn from(signal: Signal<T>) -> Self {
+        Self::from(*signal)
+    }
+}
+
+/// A collection of attributes and event listeners, spread onto an element with `{..attrs}`.
+///
+/// This lets wrapper components forward arbitrary attributes to the element they render:
+///
+/// ```
+/// # use rsx::{rsx, attributes::Attributes};
+/// fn text_input(attrs: Attributes) -> rsx::Element {
+///     rsx!(<input class="input" {..attrs} type="text"/>)
+/// }
+///
+/// let _ = text_input(
+///     Attributes::new()
+///         .attr("class", "large")
+///         .attr("required", true)
+///         .on(rsx::events::input, |_| ()),
+/// );
+/// ```
+#[derive(Default)]
+pub struct Attributes {
+    attributes: HashMap<String, MaybeDyn<AttributeValue>>,
+    event_listeners: HashMap<String, EventListener>,
+}
+
+impl Attributes {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an attribute, replacing any previous value.
+    pub fn attr(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<MaybeDyn<AttributeValue>>,
+    ) -> Self {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set an event handler, replacing any previous one for the same event.
+    pub fn on<E: EventDescriptor>(self, event: E, handler: impl FnMut(E::Event) + 'static) -> Self {
+        self.listener(E::NAME, events::listener(event, handler))
+    }
+
+    /// Set an untyped event listener, replacing any previous one for the same event.
+    pub fn listener(mut self, name: impl Into<String>, listener: EventListener) -> Self {
+        self.event_listeners.insert(name.into(), listener);
+        self
+    }
+}
+
+impl EventHandlers for Attributes {
+    fn on<E: EventDescriptor>(self, event: E, handler: impl FnMut(E::Event) + 'static) -> Self {
+        Attributes::on(self, event, handler)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Attributes
+where
+    K: Into<String>,
+    V: Into<MaybeDyn<AttributeValue>>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |attributes, (name, value)| {
+                attributes.attr(name, value)
+            })
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for Attributes
+where
+    K: Into<String>,
+    V: Into<MaybeDyn<AttributeValue>>,
+{
+    fn from(map: HashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl HTMLElement {
+    /// Merge spread attributes into this element.
+    ///
+    /// Attributes and event listeners that are already set take precedence, except for `class`
+    /// where both values are concatenated. In `rsx!`, explicit props are set first and spreads
+    /// are merged in the order they appear.
+    pub fn spread(&mut self, attributes: impl Into<Attributes>) {
+        let Attributes {
+            attributes,
+            event_listeners,
+        } = attributes.into();
+
+        for (name, value) in attributes {
+            match self.attributes.entry(name) {
+                Entry::Occupied(entry) if entry.key() == "class" => {
+                    let (name, class) = entry.remove_entry();
+                    self.attributes.insert(name, concat_classes(class, value));
+                }
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        for (name, listener) in event_listeners {
+            self.event_listeners.entry(name).or_insert(listener);
+        }
+    }
+}
+
+/// Concatenate two `class` attributes.
+fn concat_classes(
+    a: MaybeDyn<AttributeValue>,
+    b: MaybeDyn<AttributeValue>,
+) -> MaybeDyn<AttributeValue> {
+    fn concat(a: &AttributeValue, b: &AttributeValue) -> AttributeValue {
+        let classes = [a.as_str(), b.as_str()];
+        let classes = classes
+            .into_iter()
+            .flatten()
+            .filter(|class| !class.is_empty());
+        AttributeValue::String(classes.collect::<Vec<_>>().join(" "))
+    }
+
+    match (a, b) {
+        (MaybeDyn::Static(a), MaybeDyn::Static(b)) => MaybeDyn::Static(concat(&a, &b)),
+        (a, b) => MaybeDyn::Dynamic(Box::new(move || concat(&a.get(), &b.get()))),
+    }
+}
@@ -0,0 +1,93 @@
+//! Two-way bindings between DOM properties and signals.
+//!
+//! `bind:value={signal}` sets the `value` property of an element from the signal and updates the
+//! signal on every `input` event, so the two are always in sync. `bind:checked={signal}` does the
+//! same for the `checked` property of checkboxes and radio buttons, on `change` events.
+//!
+//! ```
+//! # use rsx::rsx;
+//! # use rsx_reactive::{root::create_root, signals::create_signal};
+//! # let _ = create_root(|| {
+//! let name = create_signal(String::from("Ada"));
+//! let element = rsx!(<input bind:value={name}></input>);
+//! assert_eq!(element.to_string(), r#"<input value="Ada">"#);
+//! # });
+//! ```
+//!
+//! Anything implementing [`Binding`] can be bound, such as form [`Field`](crate::form::Field)s,
+//! which are also marked as touched when the element loses focus.
+
+use rsx_reactive::signals::Signal;
+
+use crate::{
+    AttributeValue, EventListener, MaybeDyn,
+    events::{EventOptions, target_property},
+};
+
+/// A value that a DOM property can be bound to.
+pub trait Binding<T: 'static>: Copy + 'static {
+    /// The signal holding the value.
+    fn signal(self) -> Signal<T>;
+
+    /// Called when the bound element loses focus.
+    fn blur(self) {}
+}
+
+impl<T: 'static> Binding<T> for Signal<T> {
+    fn signal(self) -> Signal<T> {
+        self
+    }
+}
+
+/// A property bound to a signal, with the listeners keeping the signal up to date.
+pub struct Bound {
+    /// The name and value of the property.
+    pub property: (String, MaybeDyn<AttributeValue>),
+    /// The listeners to attach to the element, by event name.
+    pub listeners: Vec<(String, EventListener)>,
+}
+
+/// Bind the `value` property to `binding`.
+pub fn value(binding: impl Binding<String>) -> Bound {
+    bind("value", "input", binding, |value| match value {
+        AttributeValue::String(value) => Some(value),
+        AttributeValue::Bool(_) => None,
+    })
+}
+
+/// Bind the `checked` property to `binding`.
+pub fn checked(binding: impl Binding<bool>) -> Bound {
+    bind("checked", "change", binding, |value| match value {
+        AttributeValue::Bool(value) => Some(value),
+        AttributeValue::String(_) => None,
+    })
+}
+
+fn bind<T: Clone + Into<AttributeValue> + 'static>(
+    property: &'static str,
+    event: &'static str,
+    binding: impl Binding<T>,
+    from_property: fn(AttributeValue) -> Option<T>,
+) -> Bound {
+    let signal = binding.signal();
+    let update = move |event: web_sys::Event| {
+        let value = target_property(&event, property).and_then(from_property);
+        if let Some(value) = value {
+            signal.set(value);
+        }
+    };
+    Bound {
+        property: (property.to_string(), MaybeDyn::from(signal)),
+        listeners: vec![
+            (event.to_string(), listener(update)),
+            ("blur".to_string(), listener(move |_| binding.blur())),
+        ],
+    }
+}
+
+fn listener(handler: impl FnMut(web_sys::Event) + 'static) -> EventListener {
+    EventListener {
+        handler: Box::new(handler),
+        options: EventOptions::default(),
+    }
+}
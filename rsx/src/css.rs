@@ -0,0 +1,54 @@
+//! Scoped styles.
+//!
+//! [`css!`](crate::css!) creates a [`ScopedStyle`] whose rules only apply to elements with its
+//! class. `rsx!` adds the class to the elements below a `css={...}` prop and [attaches](attach)
+//! the style, which the render backend adds to the document once per class.
+
+use std::{cell::RefCell, rc::Rc};
+
+/// A stylesheet whose selectors only match elements with [`class`](Self::class). Created with
+/// [`css!`](crate::css!).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScopedStyle {
+    class: &'static str,
+    css: &'static str,
+}
+
+impl ScopedStyle {
+    /// Create a style from a stylesheet that was already scoped to `class`.
+    pub const fn new(class: &'static str, css: &'static str) -> Self {
+        Self { class, css }
+    }
+
+    /// Returns the class the stylesheet is scoped to.
+    pub fn class(self) -> &'static str {
+        self.class
+    }
+
+    /// Returns the scoped stylesheet.
+    pub fn css(self) -> &'static str {
+        self.css
+    }
+}
+
+type StyleHandler = Rc<dyn Fn(ScopedStyle)>;
+
+thread_local! {
+    static HANDLER: RefCell<Option<StyleHandler>> = const { RefCell::new(None) };
+}
+
+/// Set the function adding attached styles to the document, replacing the previous one. This is
+/// called by render backends.
+pub fn set_style_handler(handler: impl Fn(ScopedStyle) + 'static) {
+    HANDLER.with(|current| *current.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// Add `style` to the document, if a render backend has [set a handler](set_style_handler).
+///
+/// This is called by `rsx!` when an element with a `css` prop is built, so handlers are expected
+/// to add the stylesheet of a class only once however many elements attach it.
+pub fn attach(style: ScopedStyle) {
+    if let Some(handler) = HANDLER.with(|current| current.borrow().clone()) {
+        handler(style);
+    }
+}
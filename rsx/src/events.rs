@@ -0,0 +1,254 @@
+//! Typed DOM events.
+//!
+//! Every event that can be listened to from `rsx!` is described by a unit struct in this module.
+//! The struct carries the name of the event and the concrete `web_sys` type the handler receives,
+//! so `onclick` handlers get a [`web_sys::MouseEvent`], `onkeydown` handlers get a
+//! [`web_sys::KeyboardEvent`] and so on.
+//!
+//! Since the macro refers to the descriptor by path, a misspelled event name is a compile error:
+//!
+//! ```compile_fail
+//! # use rsx::rsx;
+//! let _ = rsx!(<button onclik={|_| ()}>Click me</button>);
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use web_sys::{
+    js_sys::Reflect,
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+use crate::{AttributeValue, EventListener};
+
+/// Describes a DOM event that can be listened to.
+pub trait EventDescriptor {
+    /// The type of the event passed to the handler.
+    type Event: JsCast;
+    /// The name of the event, as passed to `addEventListener`.
+    const NAME: &'static str;
+}
+
+/// Options for attaching an [`EventListener`].
+///
+/// In `rsx!`, these are set with modifiers after the event name, e.g. `on:scroll|passive` or
+/// `on:submit|prevent`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventOptions {
+    /// Dispatch the event to this listener during the capture phase (`|capture`).
+    pub capture: bool,
+    /// The listener never calls `preventDefault` (`|passive`).
+    pub passive: bool,
+    /// Remove the listener after it has been invoked once (`|once`).
+    pub once: bool,
+    /// Call `preventDefault` before running the handler (`|prevent`).
+    pub prevent_default: bool,
+    /// Call `stopPropagation` before running the handler (`|stop`).
+    pub stop_propagation: bool,
+}
+
+/// Wraps a typed event handler into an untyped [`EventListener`] with default options.
+///
+/// Events that are not an [`EventDescriptor::Event`] are ignored. The browser only dispatches
+/// events of the expected type, but scripts can dispatch e.g. a plain `Event` named `click`. The
+/// handler is also skipped when the current [`EventDispatcher`] only runs bindings.
+pub fn listener<E: EventDescriptor>(
+    _event: E,
+    mut handler: impl FnMut(E::Event) + 'static,
+) -> EventListener {
+    EventListener {
+        handler: Box::new(move |event: web_sys::Event| {
+            if dispatcher().is_some_and(|dispatcher| dispatcher.bindings_only()) {
+                return;
+            }
+            if let Some(event) = cast_event(event) {
+                handler(event);
+            }
+        }),
+        options: EventOptions::default(),
+    }
+}
+
+/// Cast `event` to `T`, or return `None` if it is not a `T`.
+#[cfg(target_arch = "wasm32")]
+fn cast_event<T: JsCast>(event: web_sys::Event) -> Option<T> {
+    event.dyn_into().ok()
+}
+
+/// Outside of wasm there is no JS to check the type of `event` with. Events can only be
+/// stand-ins dispatched by backends without a DOM, such as test renderers, so they are passed on.
+#[cfg(not(target_arch = "wasm32"))]
+fn cast_event<T: JsCast>(event: web_sys::Event) -> Option<T> {
+    Some(event.unchecked_into())
+}
+
+impl EventListener {
+    /// Replace the options of this listener.
+    pub fn with_options(self, options: EventOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Returns a listener calling this listener's handler and then `other`'s, with this
+    /// listener's options.
+    pub fn chain(self, other: EventListener) -> Self {
+        let (mut first, mut second) = (self.handler, other.handler);
+        Self {
+            handler: Box::new(move |event: web_sys::Event| {
+                first(clone_event(&event));
+                second(event);
+            }),
+            options: self.options,
+        }
+    }
+}
+
+/// Collect listeners by event name. Listeners for the same event are [chained](EventListener::chain)
+/// in order, e.g. an `on:input` handler and a `bind:value` binding on the same element.
+pub fn listeners(
+    listeners: impl IntoIterator<Item = (String, EventListener)>,
+) -> HashMap<String, EventListener> {
+    let mut map = HashMap::<String, EventListener>::new();
+    for (name, listener) in listeners {
+        let listener = match map.remove(&name) {
+            Some(existing) => existing.chain(listener),
+            None => listener,
+        };
+        map.insert(name, listener);
+    }
+    map
+}
+
+/// Dispatches events on behalf of a render backend without a DOM, such as a test renderer.
+///
+/// Such backends cannot create real `web_sys` events outside of a browser and pass stand-ins to
+/// handlers instead. Reading the target of an event, as `bind:` directives do, and copying it for
+/// [chained](EventListener::chain) handlers go through the dispatcher set with
+/// [`with_dispatcher`].
+pub trait EventDispatcher {
+    /// Returns the property `name` of the target of the event being dispatched.
+    fn target_property(&self, name: &str) -> Option<AttributeValue>;
+    /// Returns a copy of `event` for the next handler of a chain.
+    fn clone_event(&self, event: &web_sys::Event) -> web_sys::Event;
+    /// Whether only `bind:` directives should see the event. Handlers created with [`listener`],
+    /// which receive the event itself, are then skipped.
+    fn bindings_only(&self) -> bool {
+        false
+    }
+}
+
+thread_local! {
+    /// The dispatcher of the event being dispatched, if it does not come from the browser.
+    static DISPATCHER: RefCell<Option<Rc<dyn EventDispatcher>>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, which calls event handlers, with `dispatcher` as the current [`EventDispatcher`].
+pub fn with_dispatcher<T>(dispatcher: Rc<dyn EventDispatcher>, f: impl FnOnce() -> T) -> T {
+    struct Reset(Option<Rc<dyn EventDispatcher>>);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            DISPATCHER.with(|current| current.replace(self.0.take()));
+        }
+    }
+
+    let _reset = Reset(DISPATCHER.with(|current| current.replace(Some(dispatcher))));
+    f()
+}
+
+fn dispatcher() -> Option<Rc<dyn EventDispatcher>> {
+    DISPATCHER.with(|current| current.borrow().clone())
+}
+
+/// Returns the property `name` of the target of `event`, e.g. the `value` of an input. Booleans
+/// and strings are supported.
+pub fn target_property(event: &web_sys::Event, name: &str) -> Option<AttributeValue> {
+    if let Some(dispatcher) = dispatcher() {
+        return dispatcher.target_property(name);
+    }
+    let target = JsValue::from(event.target()?);
+    let value = Reflect::get(&target, &JsValue::from_str(name)).ok()?;
+    value
+        .as_bool()
+        .map(AttributeValue::Bool)
+        .or_else(|| value.as_string().map(AttributeValue::String))
+}
+
+/// Clone `event`, through the current [`EventDispatcher`] if there is one.
+fn clone_event(event: &web_sys::Event) -> web_sys::Event {
+    match dispatcher() {
+        Some(dispatcher) => dispatcher.clone_event(event),
+        None => event.clone(),
+    }
+}
+
+/// Types that event handlers can be attached to, such as [`Attributes`](crate::Attributes) or
+/// element builders.
+///
+/// Besides [`on`](EventHandlers::on), [`EventHandlersExt`] provides one method per event, e.g.
+/// `on_click`.
+pub trait EventHandlers: Sized {
+    /// Attach a handler for `event`.
+    fn on<E: EventDescriptor>(self, event: E, handler: impl FnMut(E::Event) + 'static) -> Self;
+}
+
+macro_rules! define_events {
+    (
+        events { $($ty:ty { $($name:ident),* $(,)? })* }
+        delegated { $($delegated:ident),* $(,)? }
+    ) => {
+        $($(
+            #[doc = concat!("The `", stringify!($name), "` event.")]
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy, Debug)]
+            pub struct $name;
+
+            impl EventDescriptor for $name {
+                type Event = $ty;
+                const NAME: &'static str = stringify!($name);
+            }
+        )*)*
+
+        /// One method per event, implemented for every [`EventHandlers`] type.
+        pub trait EventHandlersExt: EventHandlers {
+            $($(
+                ::paste::paste! {
+                    #[doc = concat!("Attach a handler for the `", stringify!($name), "` event.")]
+                    fn [<on_ $name>](self, handler: impl FnMut($ty) + 'static) -> Self {
+                        self.on($name, handler)
+                    }
+                }
+            )*)*
+        }
+
+        impl<T: EventHandlers> EventHandlersExt for T {}
+
+        /// Returns the name of the JS class of the event `name`, e.g. `MouseEvent` for `click`, or
+        /// `None` if `name` is not a known event.
+        ///
+        /// ```
+        /// assert_eq!(rsx::events::event_class("click"), Some("MouseEvent"));
+        /// assert_eq!(rsx::events::event_class("scroll"), Some("Event"));
+        /// ```
+        pub fn event_class(name: &str) -> Option<&'static str> {
+            match name {
+                $($(stringify!($name))|* => stringify!($ty).rsplit("::").next(),)*
+                _ => None,
+            }
+            .map(str::trim)
+        }
+
+        /// Returns whether the event `name` bubbles, so that its handlers can be delegated to the
+        /// render root.
+        ///
+        /// ```
+        /// assert!(rsx::events::is_delegated("click"));
+        /// assert!(!rsx::events::is_delegated("focus"));
+        /// ```
+        pub fn is_delegated(name: &str) -> bool {
+            matches!(name, $(stringify!($delegated))|*)
+        }
+    };
+}
+
+// The events are listed in `rsx-macros`, which also suggests their names in `rsx!`.
+rsx_macros::__events!(define_events);
@@ -0,0 +1,362 @@
+//! Forms with validation state.
+//!
+//! A form is a struct deriving [`Form`], with one [`Field`] per struct field. [`create_form`]
+//! creates the fields along with memos tracking the state of the whole form:
+//!
+//! ```
+//! # use rsx::{form::{Form, create_form}, rsx};
+//! # use rsx_reactive::root::create_root;
+//! #[derive(Default, Form)]
+//! struct Signup {
+//!     name: String,
+//!     accept: bool,
+//! }
+//!
+//! # let _ = create_root(|| {
+//! let form = create_form::<Signup>();
+//! form.fields.name.validate(|name| match name.len() {
+//!     0..3 => Err("name is too short".to_string()),
+//!     _ => Ok(()),
+//! });
+//! let view = rsx!(
+//!     <form on:submit|prevent={move |_| { form.submit(|signup| println!("{}", signup.name)); }}>
+//!         <input bind:value={form.fields.name}></input>
+//!         <input type="checkbox" bind:checked={form.fields.accept}></input>
+//!         <button disabled={move || !form.is_valid().get()}>Sign up</button>
+//!     </form>
+//! );
+//! # });
+//! ```
+//!
+//! Validation is plain reactive state, so it works the same without a DOM.
+
+use std::{future::Future, rc::Rc};
+
+use rsx_reactive::{
+    effects::create_effect,
+    futures::{spawn_local, spawn_local_scoped},
+    memos::create_selector,
+    root::untrack,
+    signals::{ReadSignal, Signal, create_signal},
+};
+
+use crate::bind::Binding;
+
+pub use rsx_macros::Form;
+
+/// A struct whose fields can be edited in a form. This is usually derived.
+///
+/// The derive generates a `<Name>Fields` struct with a public [`Field`] for every field of the
+/// struct, in the same order and with the same names.
+pub trait Form: Sized + 'static {
+    /// The fields of the form.
+    type Fields: Copy + 'static;
+
+    /// Create the fields, starting with the values of `initial`.
+    fn create_fields(initial: Self) -> Self::Fields;
+
+    /// Collect the current values of the fields.
+    fn values(fields: &Self::Fields) -> Self;
+
+    /// Returns the state of every field.
+    fn field_states(fields: &Self::Fields) -> Vec<FieldState>;
+}
+
+type Validator<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+
+/// The error and pending state of an async validator.
+type AsyncState = (ReadSignal<Option<String>>, ReadSignal<bool>);
+
+/// A field of a form: a value along with its validation state.
+///
+/// Fields can be bound to inputs with `bind:value` or `bind:checked`, which also marks them as
+/// touched when the input loses focus.
+pub struct Field<T: 'static> {
+    /// The current value.
+    pub value: Signal<T>,
+    initial: Signal<T>,
+    touched: Signal<bool>,
+    validators: Signal<Vec<Validator<T>>>,
+    async_states: Signal<Vec<AsyncState>>,
+    errors: ReadSignal<Vec<String>>,
+    dirty: ReadSignal<bool>,
+    pending: ReadSignal<bool>,
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Field<T> {}
+
+impl<T: Clone + PartialEq + 'static> Field<T> {
+    /// Create a field starting at `initial`.
+    pub fn new(initial: T) -> Self {
+        let value = create_signal(initial.clone());
+        let initial = create_signal(initial);
+        let validators = create_signal(Vec::<Validator<T>>::new());
+        let async_states = create_signal(Vec::<AsyncState>::new());
+        let errors = create_selector(move || {
+            let mut errors = value.with(|value| {
+                validators.with(|validators| {
+                    validators
+                        .iter()
+                        .filter_map(|validator| validator(value).err())
+                        .collect::<Vec<_>>()
+                })
+            });
+            async_states
+                .with(|states| errors.extend(states.iter().filter_map(|(e, _)| e.get_clone())));
+            errors
+        });
+        let pending = create_selector(move || {
+            async_states.with(|states| states.iter().any(|(_, pending)| pending.get()))
+        });
+        Self {
+            value,
+            initial,
+            touched: create_signal(false),
+            validators,
+            async_states,
+            errors,
+            dirty: create_selector(move || {
+                value.with(|value| initial.with(|initial| value != initial))
+            }),
+            pending,
+        }
+    }
+
+    /// Add a validator, returning the error message when the value is invalid.
+    pub fn validate(self, validator: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.validators
+            .update(|validators| validators.push(Rc::new(validator)));
+        self
+    }
+
+    /// Add an async validator, e.g. one checking with a server that a name is available. It is
+    /// run whenever the value changes, cancelling the previous run if it did not complete yet.
+    pub fn validate_async<F: Future<Output = Result<(), String>> + 'static>(
+        self,
+        validator: impl Fn(T) -> F + 'static,
+    ) -> Self {
+        let error = create_signal(None);
+        let pending = create_signal(false);
+        self.async_states
+            .update(|states| states.push((*error, *pending)));
+        create_effect(move || {
+            let value = self.value.get_clone();
+            untrack(|| {
+                pending.set(true);
+                let future = validator(value);
+                spawn_local_scoped(async move {
+                    error.set(future.await.err());
+                    pending.set(false);
+                });
+            });
+        });
+        self
+    }
+
+    /// Returns the messages of the failing validators. Async validators that are still running
+    /// are not included.
+    pub fn errors(self) -> ReadSignal<Vec<String>> {
+        self.errors
+    }
+
+    /// Returns `true` if the value differs from the initial value.
+    pub fn dirty(self) -> ReadSignal<bool> {
+        self.dirty
+    }
+
+    /// Returns `true` once the field has lost focus or the form was submitted.
+    pub fn touched(self) -> ReadSignal<bool> {
+        *self.touched
+    }
+
+    /// Returns `true` while an async validator is running.
+    pub fn pending(self) -> ReadSignal<bool> {
+        self.pending
+    }
+
+    /// Mark the field as touched.
+    pub fn touch(self) {
+        self.touched.set(true);
+    }
+
+    /// Set the value back to the initial value and mark the field as untouched.
+    pub fn reset(self) {
+        self.value.set(self.initial.get_clone_untracked());
+        self.touched.set(false);
+    }
+
+    /// Returns the state of this field, which is named `name` in its form.
+    pub fn state(self, name: &'static str) -> FieldState {
+        FieldState {
+            name,
+            dirty: self.dirty,
+            touched: self.touched,
+            errors: self.errors,
+            pending: self.pending,
+            reset: Rc::new(move || self.reset()),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Binding<T> for Field<T> {
+    fn signal(self) -> Signal<T> {
+        self.value
+    }
+
+    fn blur(self) {
+        self.touch();
+    }
+}
+
+/// The state of a [`Field`], independent of the type of its value.
+#[derive(Clone)]
+pub struct FieldState {
+    /// The name of the field.
+    pub name: &'static str,
+    /// See [`Field::dirty`].
+    pub dirty: ReadSignal<bool>,
+    /// See [`Field::touched`].
+    pub touched: Signal<bool>,
+    /// See [`Field::errors`].
+    pub errors: ReadSignal<Vec<String>>,
+    /// See [`Field::pending`].
+    pub pending: ReadSignal<bool>,
+    /// See [`Field::reset`].
+    pub reset: Rc<dyn Fn()>,
+}
+
+/// The fields of a form, together with the state of the whole form. See [`create_form`].
+pub struct FormState<T: Form> {
+    /// The fields of the form.
+    pub fields: T::Fields,
+    states: Signal<Vec<FieldState>>,
+    dirty: ReadSignal<bool>,
+    touched: ReadSignal<bool>,
+    errors: ReadSignal<Vec<(&'static str, String)>>,
+    is_valid: ReadSignal<bool>,
+    submitting: Signal<bool>,
+}
+
+impl<T: Form> Clone for FormState<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Form> Copy for FormState<T> {}
+
+/// Create a form starting with the [default](Default) values of `T`.
+pub fn create_form<T: Form + Default>() -> FormState<T> {
+    create_form_with(T::default())
+}
+
+/// Create a form starting with the values of `initial`.
+pub fn create_form_with<T: Form>(initial: T) -> FormState<T> {
+    let fields = T::create_fields(initial);
+    let states = create_signal(T::field_states(&fields));
+    let any = move |f: fn(&FieldState) -> bool| {
+        create_selector(move || states.with(|states| states.iter().any(f)))
+    };
+    let dirty = any(|state| state.dirty.get());
+    let touched = any(|state| state.touched.get());
+    let pending = any(|state| state.pending.get());
+    let errors = create_selector(move || {
+        states.with(|states| {
+            states
+                .iter()
+                .flat_map(|state| {
+                    let errors = state.errors.get_clone();
+                    errors.into_iter().map(|error| (state.name, error))
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let is_valid = create_selector(move || errors.with(Vec::is_empty) && !pending.get());
+    FormState {
+        fields,
+        states,
+        dirty,
+        touched,
+        errors,
+        is_valid,
+        submitting: create_signal(false),
+    }
+}
+
+impl<T: Form> FormState<T> {
+    /// Returns `true` if any field differs from its initial value.
+    pub fn dirty(self) -> ReadSignal<bool> {
+        self.dirty
+    }
+
+    /// Returns `true` if any field was touched.
+    pub fn touched(self) -> ReadSignal<bool> {
+        self.touched
+    }
+
+    /// Returns the errors of all the fields, along with the name of their field.
+    pub fn errors(self) -> ReadSignal<Vec<(&'static str, String)>> {
+        self.errors
+    }
+
+    /// Returns `true` if no field has errors and no async validator is running.
+    pub fn is_valid(self) -> ReadSignal<bool> {
+        self.is_valid
+    }
+
+    /// Returns `true` while the future passed to [`submit_async`](Self::submit_async) is running.
+    pub fn is_submitting(self) -> ReadSignal<bool> {
+        *self.submitting
+    }
+
+    /// Collect the current values of the fields.
+    pub fn values(self) -> T {
+        untrack(|| T::values(&self.fields))
+    }
+
+    /// Reset every field.
+    pub fn reset(self) {
+        for state in self.states.get_clone_untracked() {
+            (state.reset)();
+        }
+    }
+
+    /// Mark every field as touched so that their errors are shown, and call `on_valid` with the
+    /// values if the form is valid. Returns whether it was.
+    pub fn submit(self, on_valid: impl FnOnce(T)) -> bool {
+        for state in self.states.get_clone_untracked() {
+            state.touched.set(true);
+        }
+        let valid = self.is_valid.get_untracked();
+        if valid {
+            on_valid(self.values());
+        }
+        valid
+    }
+
+    /// Same as [`submit`](Self::submit) for an async handler. The form is
+    /// [submitting](Self::is_submitting) until the returned future completes.
+    pub fn submit_async<F: Future<Output = ()> + 'static>(
+        self,
+        on_valid: impl FnOnce(T) -> F,
+    ) -> bool {
+        self.submit(|values| {
+            let future = on_valid(values);
+            self.submitting.set(true);
+            // Submitting usually happens in an event handler, outside of any scope.
+            spawn_local(async move {
+                future.await;
+                // The form may have been disposed in the meantime.
+                if self.submitting.is_alive() {
+                    self.submitting.set(false);
+                }
+            });
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Serializing elements to HTML.
+//!
+//! [`Element`] implements [`Display`](fmt::Display) by rendering itself to HTML, which is what
+//! server-side rendering uses. Text and attribute values are escaped, except the text of
+//! [raw text elements](RAW_TEXT_ELEMENTS), and attributes are sorted by name, so the output is
+//! deterministic. Event listeners and node refs are not rendered, and
+//! dynamic elements and values are evaluated once. Properties are rendered as the attributes
+//! they reflect, e.g. `value` and `checked`.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use crate::{AttributeValue, Element, HTMLElement, MaybeDyn};
+
+/// HTML elements that never have children and are therefore rendered without a closing tag.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// HTML elements whose text is not parsed as markup, so it is rendered without escaping. Their
+/// content is trusted, as `</script>` or `</style>` in it would end the element early.
+pub const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Element::HTMLElement(element) => element.fmt(f),
+            Element::Text(text) => write_escaped(f, text),
+            Element::Fragment(children) => children.iter().try_for_each(|child| child.fmt(f)),
+            Element::Dynamic(element) => element().fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for HTMLElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_start_tag(f, self)?;
+        if VOID_ELEMENTS.contains(&self.name.as_str()) {
+            return Ok(());
+        }
+        match (&self.inner_html, textarea_value(self)) {
+            // Inner HTML is trusted markup, so it is not escaped.
+            (Some(inner_html), _) => f.write_str(&inner_html.get())?,
+            (None, Some(value)) => write_escaped(f, value.get().as_str().unwrap_or_default())?,
+            (None, None) if RAW_TEXT_ELEMENTS.contains(&self.name.as_str()) => self
+                .children
+                .iter()
+                .try_for_each(|child| write_raw(f, child))?,
+            (None, None) => self.children.iter().try_for_each(|child| child.fmt(f))?,
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+/// Write `element` as the content of a raw text element, with its text unescaped.
+fn write_raw(f: &mut fmt::Formatter<'_>, element: &Element) -> fmt::Result {
+    match element {
+        Element::Text(text) => f.write_str(text),
+        Element::Fragment(children) => children.iter().try_for_each(|child| write_raw(f, child)),
+        Element::Dynamic(element) => write_raw(f, &element()),
+        Element::HTMLElement(element) => fmt::Display::fmt(element, f),
+    }
+}
+
+/// Write the start tag of `element`, with its attributes.
+pub fn write_start_tag(w: &mut impl Write, element: &HTMLElement) -> fmt::Result {
+    write!(w, "<{}", element.name)?;
+    for (name, value) in attributes(element) {
+        match value {
+            AttributeValue::String(value) => {
+                write!(w, " {name}=\"")?;
+                write_escaped(w, &value)?;
+                w.write_char('"')?;
+            }
+            AttributeValue::Bool(true) => write!(w, " {name}")?,
+            AttributeValue::Bool(false) => {}
+        }
+    }
+    w.write_char('>')
+}
+
+/// The `value` property of a `<textarea>`, which is its text content in HTML.
+fn textarea_value(element: &HTMLElement) -> Option<&MaybeDyn<AttributeValue>> {
+    (element.name == "textarea")
+        .then(|| element.properties.get("value"))
+        .flatten()
+}
+
+/// Returns the current attributes of `element` sorted by name, with properties overriding the
+/// attributes of the same name, and `class:` and `style:` directives merged into the `class` and
+/// `style` attributes the same way the DOM backend applies them on top of the attributes.
+pub fn attributes(element: &HTMLElement) -> Vec<(String, AttributeValue)> {
+    let mut attributes = element
+        .attributes
+        .iter()
+        .map(|(name, value)| (name.clone(), value.get()))
+        .collect::<HashMap<_, _>>();
+    for (name, value) in &element.properties {
+        if textarea_value(element).is_none() || name != "value" {
+            attributes.insert(name.clone(), value.get());
+        }
+    }
+
+    if !element.classes.is_empty() {
+        let class = attributes.get("class").and_then(AttributeValue::as_str);
+        let class = class.unwrap_or_default();
+        let mut classes = class
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let mut directives = element.classes.iter().collect::<Vec<_>>();
+        directives.sort_unstable_by_key(|(name, _)| *name);
+        for (name, enabled) in directives {
+            let (enabled, present) = (enabled.get(), classes.contains(name));
+            if enabled && !present {
+                classes.push(name.clone());
+            } else if !enabled && present {
+                classes.retain(|class| class != name);
+            }
+        }
+        set_or_remove(&mut attributes, "class", classes.join(" "));
+    }
+
+    if !element.styles.is_empty() {
+        let style = attributes.get("style").and_then(AttributeValue::as_str);
+        let style = style.unwrap_or_default();
+        let mut declarations = style
+            .split(';')
+            .map(str::trim)
+            .filter(|declaration| !declaration.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let mut directives = element.styles.iter().collect::<Vec<_>>();
+        directives.sort_unstable_by_key(|(name, _)| *name);
+        for (name, value) in directives {
+            declarations.push(format!("{name}: {}", value.get()));
+        }
+        set_or_remove(&mut attributes, "style", declarations.join("; "));
+    }
+
+    let mut attributes = attributes.into_iter().collect::<Vec<_>>();
+    attributes.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    attributes
+}
+
+/// Set the attribute `name` to `value`, or remove it if `value` is empty.
+fn set_or_remove(attributes: &mut HashMap<String, AttributeValue>, name: &str, value: String) {
+    if value.is_empty() {
+        attributes.remove(name);
+    } else {
+        attributes.insert(name.to_string(), AttributeValue::String(value));
+    }
+}
+
+/// Write `text` escaped so that it can be used as text content or inside a double-quoted
+/// attribute.
+pub fn write_escaped(w: &mut impl Write, text: &str) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            '&' => w.write_str("&amp;")?,
+            '<' => w.write_str("&lt;")?,
+            '>' => w.write_str("&gt;")?,
+            '"' => w.write_str("&quot;")?,
+            c => w.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -1,31 +1,296 @@
+pub mod attributes;
+pub mod bind;
 pub mod component;
+pub mod css;
+pub mod events;
+pub mod form;
+pub mod html;
+pub mod maybe_dyn;
+pub mod node_ref;
 
+pub use attributes::{AttributeValue, Attributes};
+pub use maybe_dyn::MaybeDyn;
+pub use node_ref::NodeRef;
 pub use rsx_macros::*;
 
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
 
+use rsx_reactive::{
+    root::untrack,
+    signals::{ReadSignal, Signal},
+};
+
+#[allow(clippy::large_enum_variant)]
 pub enum Element {
     HTMLElement(HTMLElement),
     Text(String),
+    /// A list of elements rendered one after the other.
+    Fragment(Vec<Element>),
+    /// An element computed by a function. Render backends re-run the function whenever a signal
+    /// it reads changes.
+    Dynamic(Box<dyn Fn() -> Element>),
+}
+
+impl From<String> for Element {
+    fn from(text: String) -> Self {
+        Element::Text(text)
+    }
 }
 
-impl<T: ToString> From<T> for Element {
-    fn from(value: T) -> Self {
+impl From<Cow<'_, str>> for Element {
+    fn from(text: Cow<'_, str>) -> Self {
+        Element::Text(text.into_owned())
+    }
+}
+
+impl<T: fmt::Display + ?Sized> From<&T> for Element {
+    fn from(value: &T) -> Self {
         Element::Text(value.to_string())
     }
 }
 
+macro_rules! impl_from_display {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Element {
+                fn from(value: $ty) -> Self {
+                    Element::Text(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_display!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char
+);
+
+impl<T: Into<Element>> From<Vec<T>> for Element {
+    fn from(children: Vec<T>) -> Self {
+        Element::Fragment(children.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Element>> From<Option<T>> for Element {
+    fn from(element: Option<T>) -> Self {
+        element.map_or_else(|| Element::Fragment(Vec::new()), Into::into)
+    }
+}
+
+/// Signals are rendered as text that is updated whenever the signal changes.
+impl<T: fmt::Display> From<ReadSignal<T>> for Element {
+    fn from(signal: ReadSignal<T>) -> Self {
+        Element::Dynamic(Box::new(move || Element::Text(signal.with(T::to_string))))
+    }
+}
+
+impl<T: fmt::Display> From<Signal<T>> for Element {
+    fn from(signal: Signal<T>) -> Self {
+        Element::from(*signal)
+    }
+}
+
+impl Element {
+    /// Returns a copy of the element where dynamic elements and values are replaced with their
+    /// current value, read without tracking signals. Event listeners are left out.
+    ///
+    /// This is meant for tests, to compare the current value of elements:
+    ///
+    /// ```
+    /// # use rsx::rsx;
+    /// # use rsx_reactive::{root::create_root, signals::create_signal};
+    /// # let _ = create_root(|| {
+    /// let count = create_signal(1);
+    /// let element = rsx!(<p>{move || count.get()}</p>);
+    /// assert_ne!(element, rsx!(<p>1</p>));
+    /// assert_eq!(element.resolve(), rsx!(<p>1</p>));
+    /// # });
+    /// ```
+    pub fn resolve(&self) -> Element {
+        match self {
+            Element::HTMLElement(element) => Element::HTMLElement(element.resolve()),
+            Element::Text(text) => Element::Text(text.clone()),
+            Element::Fragment(children) => {
+                Element::Fragment(children.iter().map(Element::resolve).collect())
+            }
+            Element::Dynamic(f) => untrack(f).resolve(),
+        }
+    }
+}
+
+impl HTMLElement {
+    /// See [`Element::resolve`].
+    fn resolve(&self) -> HTMLElement {
+        fn resolve<T: Clone>(
+            values: &HashMap<String, MaybeDyn<T>>,
+        ) -> HashMap<String, MaybeDyn<T>> {
+            values
+                .iter()
+                .map(|(name, value)| (name.clone(), value.resolve()))
+                .collect()
+        }
+
+        HTMLElement {
+            name: self.name.clone(),
+            attributes: resolve(&self.attributes),
+            event_listeners: HashMap::new(),
+            classes: resolve(&self.classes),
+            styles: resolve(&self.styles),
+            properties: resolve(&self.properties),
+            inner_html: self.inner_html.as_ref().map(MaybeDyn::resolve),
+            node_ref: self.node_ref,
+            children: self.children.iter().map(Element::resolve).collect(),
+        }
+    }
+}
+
 impl From<HTMLElement> for Element {
     fn from(value: HTMLElement) -> Self {
         Element::HTMLElement(value)
     }
 }
 
-pub type EventListener = Box<dyn Fn(web_sys::Event)>;
+/// A type-erased event handler together with the options it should be attached with. Typed
+/// handlers are converted with [`events::listener`].
+pub struct EventListener {
+    pub handler: Box<dyn FnMut(web_sys::Event)>,
+    pub options: events::EventOptions,
+}
 
 pub struct HTMLElement {
     pub name: String,
-    pub attributes: HashMap<String, String>,
+    pub attributes: HashMap<String, MaybeDyn<AttributeValue>>,
     pub event_listeners: HashMap<String, EventListener>,
+    /// Classes toggled with `class:<name>={...}`, on top of the `class` attribute, and the classes
+    /// of the scoped styles passed with `css={...}` to this element or its ancestors.
+    pub classes: HashMap<String, MaybeDyn<bool>>,
+    /// CSS properties set with `style:<name>={...}`, on top of the `style` attribute.
+    pub styles: HashMap<String, MaybeDyn<String>>,
+    /// DOM properties set with `prop:<name>={...}` or `bind:<name>={...}`. They are rendered as
+    /// attributes on the server.
+    pub properties: HashMap<String, MaybeDyn<AttributeValue>>,
+    /// Markup set with `dangerously_set_inner_html={...}`, inserted as is instead of children.
+    pub inner_html: Option<MaybeDyn<String>>,
+    /// The reference set with `ref={...}`, filled in with the node once it is created.
+    pub node_ref: Option<NodeRef>,
     pub children: Vec<Element>,
 }
+
+impl fmt::Debug for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Element::HTMLElement(element) => element.fmt(f),
+            Element::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Element::Fragment(children) => f.debug_tuple("Fragment").field(children).finish(),
+            // Calling the function could have side effects, and track signals.
+            Element::Dynamic(_) => f.write_str("Dynamic(..)"),
+        }
+    }
+}
+
+/// Attributes are shown sorted by name and event listeners by their event name only.
+impl fmt::Debug for HTMLElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut event_listeners = self.event_listeners.keys().collect::<Vec<_>>();
+        event_listeners.sort_unstable();
+        f.debug_struct("HTMLElement")
+            .field("name", &self.name)
+            .field(
+                "attributes",
+                &self.attributes.iter().collect::<BTreeMap<_, _>>(),
+            )
+            .field("classes", &self.classes.iter().collect::<BTreeMap<_, _>>())
+            .field("styles", &self.styles.iter().collect::<BTreeMap<_, _>>())
+            .field(
+                "properties",
+                &self.properties.iter().collect::<BTreeMap<_, _>>(),
+            )
+            .field("event_listeners", &event_listeners)
+            .field("inner_html", &self.inner_html)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for EventListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventListener")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Dynamic elements are never equal, not even to themselves, since comparing them would call their
+/// function. Compare [resolved](Element::resolve) elements to compare their current value.
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Element::HTMLElement(a), Element::HTMLElement(b)) => a == b,
+            (Element::Text(a), Element::Text(b)) => a == b,
+            (Element::Fragment(a), Element::Fragment(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Event listeners and node refs are ignored.
+impl PartialEq for HTMLElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.attributes == other.attributes
+            && self.classes == other.classes
+            && self.styles == other.styles
+            && self.properties == other.properties
+            && self.inner_html == other.inner_html
+            && self.children == other.children
+    }
+}
+
+/// The conversion of the children of [`rsx!`], which are converted with [`Into<Element>`] if
+/// they can be, and rendered as text otherwise if they implement [`Display`](fmt::Display).
+///
+/// Both cannot be implemented with `From` since `Element` implements `Display` itself, so this
+/// relies on method resolution preferring the first trait, whose receiver needs one less
+/// reference.
+#[doc(hidden)]
+pub mod __private {
+    use std::{cell::Cell, fmt};
+
+    use crate::Element;
+
+    pub struct Child<T>(Cell<Option<T>>);
+
+    impl<T> Child<T> {
+        pub fn new(value: T) -> Self {
+            Self(Cell::new(Some(value)))
+        }
+
+        fn take(&self) -> T {
+            self.0.take().expect("child converted twice")
+        }
+    }
+
+    pub trait ViaInto {
+        fn to_element(&self) -> Element;
+    }
+
+    impl<T: Into<Element>> ViaInto for Child<T> {
+        fn to_element(&self) -> Element {
+            self.take().into()
+        }
+    }
+
+    pub trait ViaDisplay {
+        fn to_element(&self) -> Element;
+    }
+
+    impl<T: fmt::Display> ViaDisplay for &Child<T> {
+        fn to_element(&self) -> Element {
+            Element::Text(self.take().to_string())
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Values that are either fixed or recomputed from signals.
+
+use std::fmt;
+
+use rsx_reactive::{
+    effects::create_effect,
+    root::untrack,
+    signals::{ReadSignal, Signal},
+};
+
+/// A value that is either known up front or computed by a function.
+///
+/// Render backends read dynamic values inside an effect, so that whatever depends on them is
+/// updated whenever a signal read by the function changes.
+pub enum MaybeDyn<T> {
+    Static(T),
+    Dynamic(Box<dyn Fn() -> T>),
+}
+
+impl<T: Clone> MaybeDyn<T> {
+    /// Returns the current value, tracking the signals it depends on.
+    pub fn get(&self) -> T {
+        match self {
+            Self::Static(value) => value.clone(),
+            Self::Dynamic(f) => f(),
+        }
+    }
+}
+
+impl<T: Clone> MaybeDyn<T> {
+    /// Returns the current value as a static value, without tracking the signals it depends on.
+    pub fn resolve(&self) -> Self {
+        Self::Static(untrack(|| self.get()))
+    }
+}
+
+impl<T: 'static> MaybeDyn<T> {
+    /// Call `f` with the value, and again whenever a dynamic value changes.
+    pub fn apply(self, mut f: impl FnMut(T) + 'static) {
+        match self {
+            Self::Static(value) => f(value),
+            Self::Dynamic(value) => create_effect(move || f(value())),
+        }
+    }
+}
+
+/// Dynamic values are shown as `Dynamic(..)`, without calling their function.
+impl<T: fmt::Debug> fmt::Debug for MaybeDyn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static(value) => value.fmt(f),
+            Self::Dynamic(_) => f.write_str("Dynamic(..)"),
+        }
+    }
+}
+
+/// Dynamic values are never equal, since comparing them would call their function.
+impl<T: PartialEq> PartialEq for MaybeDyn<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<bool> for MaybeDyn<bool> {
+    fn from(value: bool) -> Self {
+        Self::Static(value)
+    }
+}
+
+impl<F: Fn() -> bool + 'static> From<F> for MaybeDyn<bool> {
+    fn from(f: F) -> Self {
+        Self::Dynamic(Box::new(f))
+    }
+}
+
+impl From<ReadSignal<bool>> for MaybeDyn<bool> {
+    fn from(signal: ReadSignal<bool>) -> Self {
+        Self::Dynamic(Box::new(move || signal.get()))
+    }
+}
+
+impl From<Signal<bool>> for MaybeDyn<bool> {
+    fn from(signal: Signal<bool>) -> Self {
+        Self::from(*signal)
+    }
+}
+
+impl From<String> for MaybeDyn<String> {
+    fn from(value: String) -> Self {
+        Self::Static(value)
+    }
+}
+
+impl From<&str> for MaybeDyn<String> {
+    fn from(value: &str) -> Self {
+        Self::Static(value.to_string())
+    }
+}
+
+impl From<&String> for MaybeDyn<String> {
+    fn from(value: &String) -> Self {
+        Self::Static(value.clone())
+    }
+}
+
+impl<F: Fn() -> U + 'static, U: ToString> From<F> for MaybeDyn<String> {
+    fn from(f: F) -> Self {
+        Self::Dynamic(Box::new(move || f().to_string()))
+    }
+}
+
+impl<T: ToString> From<ReadSignal<T>> for MaybeDyn<String> {
+    fn from(signal: ReadSignal<T>) -> Self {
+        Self::Dynamic(Box::new(move || signal.with(T::to_string)))
+    }
+}
+
+impl<T: ToString> From<Signal<T>> for MaybeDyn<String> {
+    fn from(signal: Signal<T>) -> Self {
+        Self::from(*signal)
+    }
+}
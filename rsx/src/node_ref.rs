@@ -0,0 +1,62 @@
+//! Handles to rendered DOM nodes.
+
+use rsx_reactive::signals::{Signal, create_signal};
+use web_sys::wasm_bindgen::JsCast;
+
+/// A reference to the node rendered for an element, filled in with the `ref={...}` prop.
+///
+/// The reference is backed by a signal: reading it inside an effect or a memo tracks it, so they
+/// re-run once the node is created, and again once it is cleared when the scope that rendered the
+/// element is disposed. Note that the node is set before it is inserted into the document. It
+/// stays empty when rendering to a string.
+///
+/// ```
+/// # use rsx::{rsx, node_ref::create_node_ref};
+/// # use rsx_reactive::{effects::create_effect, root::create_root};
+/// # let _ = create_root(|| {
+/// let input = create_node_ref();
+/// create_effect(move || {
+///     if let Some(input) = input.get::<web_sys::Element>() {
+///         // Focus the input, measure it, hand it over to a widget, ...
+///     }
+/// });
+/// let _ = rsx!(<input ref={input}/>);
+/// # });
+/// ```
+pub struct NodeRef<T: 'static = web_sys::Node>(Signal<Option<T>>);
+
+/// Create a new, empty [`NodeRef`].
+pub fn create_node_ref<T>() -> NodeRef<T> {
+    NodeRef(create_signal(None))
+}
+
+impl<T: Clone> NodeRef<T> {
+    /// Returns the node, or `None` if the element is not rendered. This tracks the reference.
+    pub fn get_raw(self) -> Option<T> {
+        self.0.get_clone()
+    }
+
+    /// Set the node. This is called by render backends and does nothing once the reference has
+    /// been disposed.
+    pub fn set(self, node: Option<T>) {
+        if self.0.is_alive() {
+            self.0.set(node);
+        }
+    }
+}
+
+impl<T: Clone + JsCast> NodeRef<T> {
+    /// Returns the node cast to `U`, e.g. a [`web_sys::HtmlInputElement`], or `None` if the
+    /// element is not rendered or its node is not a `U`. This tracks the reference.
+    pub fn get<U: JsCast>(self) -> Option<U> {
+        self.get_raw().and_then(|node| node.dyn_into().ok())
+    }
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeRef<T> {}
@@ -0,0 +1,236 @@
+//! Dispatching events to the handlers of the in-memory tree.
+
+use std::{cell::Cell, rc::Rc};
+
+use rsx::{
+    AttributeValue,
+    events::{EventDispatcher, event_class, with_dispatcher},
+};
+use web_sys::{
+    js_sys::{Array, Function, Object, Reflect, global},
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+use crate::node::TestNode;
+
+/// An event fired with [`fire_event`].
+///
+/// Data such as the value of an input is set on the target, where `bind:` directives read it.
+/// Handlers receive a [stand-in](fire_event) for the event itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A click. Clicking a checkbox or radio button checks it and fires `input` and `change`,
+    /// and clicking a submit button submits its form, unless the click is prevented.
+    Click,
+    /// A double click.
+    DoubleClick,
+    /// Typing `value` into an input: sets its `value` property and fires `input`.
+    Input(String),
+    /// Committing `value` to an input or select: sets its `value` property and fires `change`.
+    Change(String),
+    /// Submitting a form.
+    Submit,
+    /// Focusing an element. Does not bubble.
+    Focus,
+    /// An element losing focus. Does not bubble.
+    Blur,
+    /// Any other event, by name. It bubbles.
+    Named(&'static str),
+}
+
+/// Fire `event` at `target`, running the handlers of `target` and its ancestors as the browser
+/// would: capturing listeners first, then bubbling up from the target. Effects run
+/// synchronously, so the tree is up to date once this returns.
+///
+/// Returns `false` if a handler prevented the default action, e.g. with `on:click|prevent`.
+///
+/// When compiled to wasm, handlers receive a real event of the type they expect, e.g. a
+/// `MouseEvent` for `click`, on which they can call `preventDefault` and `stopPropagation`.
+/// Elsewhere `web_sys` events cannot be created, so handlers receive a placeholder that must not
+/// be used: calling any of its methods panics. The `prevent` and `stop` modifiers work in both
+/// cases. To fill in an input whose handlers use the event, use [`fire_input`] instead.
+pub fn fire_event(target: &TestNode, event: Event) -> bool {
+    match event {
+        Event::Click => click(target),
+        Event::DoubleClick => dispatch(target, "dblclick", true),
+        Event::Input(value) => {
+            target.set_property("value", value);
+            dispatch(target, "input", true)
+        }
+        Event::Change(value) => {
+            target.set_property("value", value);
+            dispatch(target, "change", true)
+        }
+        Event::Submit => dispatch(target, "submit", true),
+        Event::Focus => dispatch(target, "focus", false),
+        Event::Blur => dispatch(target, "blur", false),
+        Event::Named(name) => dispatch(target, name, true),
+    }
+}
+
+/// Type `value` into `target`: sets its `value` property and updates its `bind:value` binding,
+/// without running any other handler. Unlike [`Event::Input`], this works outside of wasm with
+/// handlers that use the event, e.g. to read its target or call `preventDefault`.
+///
+/// ```
+/// # use rsx::rsx;
+/// # use rsx_reactive::signals::create_signal;
+/// # use rsx_testing::{fire_input, render};
+/// let screen = render(|| {
+///     let name = create_signal(String::new());
+///     rsx!(
+///         <input bind:value={name} oninput={|event| event.prevent_default()}></input>
+///         <p>Hello {name}</p>
+///     )
+/// });
+/// fire_input(&screen.get_by_role("textbox"), "Ada");
+/// assert_eq!(screen.get_by_text("Hello Ada").text_content(), "Hello Ada");
+/// ```
+pub fn fire_input(target: &TestNode, value: impl Into<String>) {
+    target.set_property("value", value.into());
+    let dispatcher = Dispatcher {
+        target: target.clone(),
+        bindings_only: true,
+    };
+    dispatch_with(Rc::new(dispatcher), "input", false);
+}
+
+fn click(target: &TestNode) -> bool {
+    let input_type = target.attribute("type").unwrap_or_default();
+    let checkable = target.tag().as_deref() == Some("input")
+        && matches!(input_type.as_str(), "checkbox" | "radio");
+    let was_checked = target.property("checked") == Some(AttributeValue::Bool(true));
+    if checkable {
+        target.set_property("checked", input_type == "radio" || !was_checked);
+    }
+
+    let not_prevented = dispatch(target, "click", true);
+    if checkable {
+        if not_prevented {
+            dispatch(target, "input", true);
+            dispatch(target, "change", true);
+        } else {
+            target.set_property("checked", was_checked);
+        }
+    } else if not_prevented
+        && target.tag().as_deref() == Some("button")
+        && !matches!(input_type.as_str(), "button" | "reset")
+        && let Some(form) = ancestors(target).find(|node| node.tag().as_deref() == Some("form"))
+    {
+        dispatch(&form, "submit", true);
+    }
+    not_prevented
+}
+
+fn ancestors(node: &TestNode) -> impl Iterator<Item = TestNode> {
+    std::iter::successors(node.parent(), TestNode::parent)
+}
+
+/// Dispatches an event to the handlers of the tree, reading the properties of its target.
+struct Dispatcher {
+    target: TestNode,
+    /// Whether only bindings see the event, see [`fire_input`].
+    bindings_only: bool,
+}
+
+impl EventDispatcher for Dispatcher {
+    fn target_property(&self, name: &str) -> Option<AttributeValue> {
+        self.target.property(name)
+    }
+
+    fn clone_event(&self, event: &web_sys::Event) -> web_sys::Event {
+        match cfg!(target_arch = "wasm32") {
+            true => event.clone(),
+            false => placeholder(),
+        }
+    }
+
+    fn bindings_only(&self) -> bool {
+        self.bindings_only
+    }
+}
+
+/// The event passed to handlers outside of wasm.
+fn placeholder() -> web_sys::Event {
+    JsValue::UNDEFINED.unchecked_into()
+}
+
+/// Create the event `name` of the class its handlers expect, or a placeholder outside of wasm.
+fn create_event(name: &str, bubbles: bool) -> web_sys::Event {
+    if !cfg!(target_arch = "wasm32") {
+        return placeholder();
+    }
+    // Some classes, such as `TouchEvent`, do not exist in every browser.
+    let class = event_class(name).unwrap_or("Event");
+    let constructor = [class, "Event"]
+        .into_iter()
+        .map(|class| Reflect::get(&global(), &JsValue::from_str(class)).unwrap())
+        .find(JsValue::is_function)
+        .unwrap();
+    let init = Object::new();
+    Reflect::set(
+        &init,
+        &JsValue::from_str("bubbles"),
+        &JsValue::from_bool(bubbles),
+    )
+    .unwrap();
+    Reflect::set(&init, &JsValue::from_str("cancelable"), &JsValue::TRUE).unwrap();
+    let arguments = Array::of2(&JsValue::from_str(name), &init);
+    Reflect::construct(constructor.unchecked_ref::<Function>(), &arguments)
+        .unwrap()
+        .unchecked_into()
+}
+
+/// Dispatch the event `name` to `target`. Returns `false` if the default action was prevented.
+fn dispatch(target: &TestNode, name: &str, bubbles: bool) -> bool {
+    let dispatcher = Dispatcher {
+        target: target.clone(),
+        bindings_only: false,
+    };
+    dispatch_with(Rc::new(dispatcher), name, bubbles)
+}
+
+/// Dispatch the event `name` to the target of `dispatcher`.
+fn dispatch_with(dispatcher: Rc<Dispatcher>, name: &str, bubbles: bool) -> bool {
+    let target = dispatcher.target.clone();
+    let event = create_event(name, bubbles);
+    let prevented = Cell::new(false);
+    let stopped = Cell::new(false);
+    let path = ancestors(&target).collect::<Vec<_>>();
+
+    let invoke = |node: &TestNode, capture: Option<bool>| {
+        for listener in node.listeners(name) {
+            if capture.is_some_and(|capture| capture != listener.options.capture) {
+                continue;
+            }
+            // Bindings have no options, those of a listener come from the handlers it chains.
+            if !dispatcher.bindings_only {
+                if listener.options.once {
+                    node.remove_listener(&listener);
+                }
+                if listener.options.prevent_default {
+                    prevented.set(true);
+                }
+                if listener.options.stop_propagation {
+                    stopped.set(true);
+                }
+            }
+            let event = dispatcher.clone_event(&event);
+            with_dispatcher(dispatcher.clone(), || {
+                (listener.handler.borrow_mut())(event)
+            });
+        }
+        // Real events also record calls to `preventDefault` and `stopPropagation`.
+        if cfg!(target_arch = "wasm32") {
+            prevented.set(prevented.get() || event.default_prevented());
+            stopped.set(stopped.get() || event.cancel_bubble());
+        }
+        !stopped.get()
+    };
+
+    let _ = path.iter().rev().all(|node| invoke(node, Some(true)))
+        && invoke(&target, None)
+        && bubbles
+        && path.iter().all(|node| invoke(node, Some(false)));
+    !prevented.get()
+}
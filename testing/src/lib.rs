@@ -0,0 +1,121 @@
+//! Testing components without a browser.
+//!
+//! [`render`] renders a component into an in-memory tree instead of the DOM, so component tests
+//! run with plain `cargo test`. Effects run synchronously as in the browser, [`fire_event`]
+//! dispatches synthetic events to the handlers of the tree, and elements are found with
+//! [queries](queries) such as [`get_by_text`](TestNode::get_by_text) and
+//! [`get_by_role`](TestNode::get_by_role):
+//!
+//! ```
+//! # use rsx::rsx;
+//! # use rsx_reactive::signals::create_signal;
+//! # use rsx_testing::{Event, assert_snapshot, fire_event, render};
+//! fn counter() -> rsx::Element {
+//!     let count = create_signal(0);
+//!     rsx!(
+//!         <div>
+//!             <p data-testid="count">Count: {count}</p>
+//!             <button onclick={move |_| count.set(count.get() + 1)}>Increment</button>
+//!         </div>
+//!     )
+//! }
+//!
+//! let screen = render(counter);
+//! fire_event(&screen.get_by_role("button"), Event::Click);
+//! assert_eq!(screen.get_by_test_id("count").text_content(), "Count: 1");
+//! assert_snapshot!(screen, @r#"
+//!     <div>
+//!       <p data-testid="count">
+//!         Count:
+//!         1
+//!       </p>
+//!       <button>
+//!         Increment
+//!       </button>
+//!     </div>
+//! "#);
+//! ```
+//!
+//! The tree is built by the same code as the DOM, through the `ViewHtmlNode` backend trait of
+//! `rsx-web`. Input values are set on the target by [`Event::Input`] and [`Event::Change`], where
+//! `bind:` directives read them. Outside of wasm, handlers receive a placeholder instead of a
+//! `web_sys` event, see [`fire_event`], and [`fire_input`] fills in inputs without one.
+
+mod events;
+mod node;
+pub mod queries;
+#[doc(hidden)]
+pub mod snapshot;
+
+use std::{fmt, ops::Deref};
+
+use rsx_reactive::{
+    context::provide_context,
+    root::{RootHandle, create_root},
+};
+use rsx_web::{
+    head::HeadManager,
+    view::{View, ViewNode},
+};
+
+pub use events::{Event, fire_event, fire_input};
+pub use node::TestNode;
+
+/// A component rendered with [`render`]. Queries on the screen search the whole rendered tree.
+pub struct Screen {
+    root: RootHandle,
+    container: TestNode,
+    head: HeadManager,
+}
+
+/// Render a component into a new in-memory tree, inside its own reactive root.
+///
+/// The [head tags](rsx_web::head) registered by the component, including the stylesheets of its
+/// `css={...}` props, are collected by the screen's own [`HeadManager`].
+pub fn render<C: FnOnce() -> rsx::Element>(component: C) -> Screen {
+    rsx_web::head::install_style_handler();
+    let container = TestNode::group();
+    let head = HeadManager::new();
+    let mut content = container.clone();
+    let root = create_root(|| {
+        provide_context(head.clone());
+        content.append_view(View::from(component()))
+    });
+    Screen {
+        root,
+        container,
+        head,
+    }
+}
+
+impl Screen {
+    /// Returns the node holding the rendered component.
+    pub fn container(&self) -> &TestNode {
+        &self.container
+    }
+
+    /// Returns the manager collecting the head tags of the component.
+    pub fn head(&self) -> &HeadManager {
+        &self.head
+    }
+
+    /// Dispose the reactive root of the component, running its cleanups, and clear the tree.
+    pub fn unmount(self) {
+        self.root.dispose();
+        self.container.clear();
+    }
+}
+
+impl Deref for Screen {
+    type Target = TestNode;
+
+    fn deref(&self) -> &TestNode {
+        &self.container
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.container.fmt(f)
+    }
+}
@@ -0,0 +1,473 @@
+//! The in-memory tree.
+
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::{self, Write},
+    rc::{Rc, Weak},
+};
+
+use rsx::{
+    AttributeValue, HTMLElement, MaybeDyn,
+    events::EventOptions,
+    html::{self, RAW_TEXT_ELEMENTS, VOID_ELEMENTS, write_escaped},
+};
+use rsx_reactive::{effects::create_effect, root::on_cleanup};
+use rsx_web::{
+    node::ViewHtmlNode,
+    view::{View, ViewNode},
+};
+
+/// A node of the in-memory tree rendered by [`render`](crate::render), implementing the render
+/// backend traits of `rsx-web`.
+///
+/// Nodes are shared: cloning a node returns another handle to the same node, and two handles are
+/// equal if they point to the same node. They are printed the same way as server-side rendering
+/// prints elements, and indented with `{:#}`.
+#[derive(Clone)]
+pub struct TestNode(Rc<RefCell<NodeData>>);
+
+struct NodeData {
+    kind: Kind,
+    parent: Weak<RefCell<NodeData>>,
+    children: Vec<TestNode>,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Kind {
+    Element(ElementData),
+    Text(String),
+    /// A container that is not part of the document, holding the content of a dynamic element
+    /// or of a whole screen. Its children are treated as children of its parent.
+    Group,
+}
+
+#[derive(Default)]
+struct ElementData {
+    name: String,
+    attributes: HashMap<String, AttributeValue>,
+    classes: HashMap<String, bool>,
+    styles: HashMap<String, String>,
+    properties: HashMap<String, AttributeValue>,
+    inner_html: Option<String>,
+    listeners: Vec<Listener>,
+}
+
+type Handler = Rc<RefCell<Box<dyn FnMut(web_sys::Event)>>>;
+
+thread_local! {
+    /// The id of the next listener, used to remove it.
+    static NEXT_LISTENER_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An event listener attached to an element.
+#[derive(Clone)]
+pub(crate) struct Listener {
+    id: usize,
+    pub(crate) event: String,
+    pub(crate) handler: Handler,
+    pub(crate) options: EventOptions,
+}
+
+impl TestNode {
+    fn new(kind: Kind) -> Self {
+        Self(Rc::new(RefCell::new(NodeData {
+            kind,
+            parent: Weak::new(),
+            children: Vec::new(),
+        })))
+    }
+
+    pub(crate) fn group() -> Self {
+        Self::new(Kind::Group)
+    }
+
+    /// Returns the tag name of this node, or `None` if it is a text node.
+    pub fn tag(&self) -> Option<String> {
+        match &self.0.borrow().kind {
+            Kind::Element(element) => Some(element.name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the attribute `name`, with `class:`, `style:` and properties merged in the same
+    /// way as server-side rendering. Boolean attributes that are set have an empty value.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.with_element(|element| {
+            html::attributes(&element.to_html())
+                .into_iter()
+                .find(|(attribute, _)| attribute == name)
+        })
+        .flatten()
+        .and_then(|(_, value)| match value {
+            AttributeValue::String(value) => Some(value),
+            AttributeValue::Bool(true) => Some(String::new()),
+            AttributeValue::Bool(false) => None,
+        })
+    }
+
+    /// Returns the DOM property `name`. The `value` and `checked` properties default to their
+    /// attribute, as in the DOM.
+    pub fn property(&self, name: &str) -> Option<AttributeValue> {
+        self.with_element(|element| match element.properties.get(name) {
+            Some(value) => Some(value.clone()),
+            None if name == "value" => element.attributes.get(name).cloned(),
+            None if name == "checked" => Some(AttributeValue::Bool(matches!(
+                element.attributes.get(name),
+                Some(AttributeValue::Bool(true) | AttributeValue::String(_))
+            ))),
+            None => None,
+        })
+        .flatten()
+    }
+
+    /// Set the DOM property `name`, as the browser does for e.g. the `value` of an input that
+    /// is typed into.
+    pub fn set_property(&self, name: &str, value: impl Into<AttributeValue>) {
+        self.with_element_mut(|element| {
+            element.properties.insert(name.to_string(), value.into());
+        });
+    }
+
+    /// Returns the text of this node and all of its descendants.
+    pub fn text_content(&self) -> String {
+        let data = self.0.borrow();
+        match &data.kind {
+            Kind::Text(text) => text.clone(),
+            _ => data.children.iter().map(TestNode::text_content).collect(),
+        }
+    }
+
+    /// Returns the children of this node.
+    pub fn children(&self) -> Vec<TestNode> {
+        let mut children = Vec::new();
+        for child in &self.0.borrow().children {
+            match child.0.borrow().kind {
+                Kind::Group => children.extend(child.children()),
+                _ => children.push(child.clone()),
+            }
+        }
+        children
+    }
+
+    /// Returns the parent element of this node, if it is attached to one.
+    pub fn parent(&self) -> Option<TestNode> {
+        let parent = TestNode(self.0.borrow().parent.upgrade()?);
+        match parent.0.borrow().kind {
+            Kind::Group => parent.parent(),
+            _ => Some(parent.clone()),
+        }
+    }
+
+    /// Returns `true` if this is an element rather than a text node.
+    pub(crate) fn is_element(&self) -> bool {
+        matches!(self.0.borrow().kind, Kind::Element(_))
+    }
+
+    /// Returns the text of the text nodes directly inside this node.
+    pub(crate) fn own_text(&self) -> String {
+        let mut text = String::new();
+        for child in self.children() {
+            if let Kind::Text(value) = &child.0.borrow().kind {
+                text += value;
+            }
+        }
+        text
+    }
+
+    /// Returns the elements inside this node, in document order.
+    pub(crate) fn descendants(&self) -> Vec<TestNode> {
+        let mut descendants = Vec::new();
+        for child in self.children() {
+            if child.is_element() {
+                descendants.push(child.clone());
+                descendants.extend(child.descendants());
+            }
+        }
+        descendants
+    }
+
+    /// Returns the listeners for `event`.
+    pub(crate) fn listeners(&self, event: &str) -> Vec<Listener> {
+        self.with_element(|element| {
+            element
+                .listeners
+                .iter()
+                .filter(|listener| listener.event == event)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    pub(crate) fn remove_listener(&self, listener: &Listener) {
+        self.with_element_mut(|element| element.listeners.retain(|l| l.id != listener.id));
+    }
+
+    fn append(&self, child: TestNode) {
+        child.0.borrow_mut().parent = Rc::downgrade(&self.0);
+        self.0.borrow_mut().children.push(child);
+    }
+
+    /// Detach every child of this node.
+    pub(crate) fn clear(&self) {
+        for child in std::mem::take(&mut self.0.borrow_mut().children) {
+            child.0.borrow_mut().parent = Weak::new();
+        }
+    }
+
+    fn with_element<T>(&self, f: impl FnOnce(&ElementData) -> T) -> Option<T> {
+        match &self.0.borrow().kind {
+            Kind::Element(element) => Some(f(element)),
+            _ => None,
+        }
+    }
+
+    fn with_element_mut(&self, f: impl FnOnce(&mut ElementData)) {
+        if let Kind::Element(element) = &mut self.0.borrow_mut().kind {
+            f(element);
+        }
+    }
+
+    /// Returns the markup replacing the children of `element`, the data of this node: the content
+    /// of the element, or the unescaped text of a raw text element.
+    fn content(&self, element: &ElementData) -> Option<String> {
+        element.content().or_else(|| {
+            RAW_TEXT_ELEMENTS
+                .contains(&element.name.as_str())
+                .then(|| self.text_content())
+        })
+    }
+
+    fn fmt_pretty(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        let data = self.0.borrow();
+        match &data.kind {
+            Kind::Group => {
+                for child in &data.children {
+                    child.fmt_pretty(f, depth)?;
+                }
+            }
+            Kind::Text(text) if text.trim().is_empty() => {}
+            Kind::Text(text) => {
+                f.write_str(&indent)?;
+                write_escaped(f, text.trim())?;
+                f.write_char('\n')?;
+            }
+            Kind::Element(element) => {
+                f.write_str(&indent)?;
+                html::write_start_tag(f, &element.to_html())?;
+                if VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    return f.write_char('\n');
+                }
+                let content = self.content(element);
+                if content.is_none() && self.text_content().trim().is_empty() {
+                    return writeln!(f, "</{}>", element.name);
+                }
+                f.write_char('\n')?;
+                match content {
+                    Some(content) => writeln!(f, "{indent}  {}", content.trim())?,
+                    None => {
+                        for child in &data.children {
+                            child.fmt_pretty(f, depth + 1)?;
+                        }
+                    }
+                }
+                writeln!(f, "{indent}</{}>", element.name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ElementData {
+    /// Returns a static copy of this element, without children, to serialize its attributes.
+    fn to_html(&self) -> HTMLElement {
+        fn copy<T: Clone>(values: &HashMap<String, T>) -> HashMap<String, MaybeDyn<T>> {
+            values
+                .iter()
+                .map(|(name, value)| (name.clone(), MaybeDyn::Static(value.clone())))
+                .collect()
+        }
+        HTMLElement {
+            name: self.name.clone(),
+            attributes: copy(&self.attributes),
+            event_listeners: HashMap::new(),
+            classes: copy(&self.classes),
+            styles: copy(&self.styles),
+            properties: copy(&self.properties),
+            inner_html: None,
+            node_ref: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the markup replacing the children of this element: its inner HTML, or the escaped
+    /// value of a `<textarea>`.
+    fn content(&self) -> Option<String> {
+        if let Some(inner_html) = &self.inner_html {
+            return Some(inner_html.clone());
+        }
+        let value = self
+            .properties
+            .get("value")
+            .filter(|_| self.name == "textarea")?;
+        let mut content = String::new();
+        write_escaped(&mut content, value.as_str().unwrap_or_default()).unwrap();
+        Some(content)
+    }
+}
+
+impl PartialEq for TestNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for TestNode {}
+
+impl fmt::Display for TestNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.fmt_pretty(f, 0);
+        }
+        let data = self.0.borrow();
+        match &data.kind {
+            Kind::Text(text) => write_escaped(f, text),
+            Kind::Group => data.children.iter().try_for_each(|child| child.fmt(f)),
+            Kind::Element(element) => {
+                html::write_start_tag(f, &element.to_html())?;
+                if VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    return Ok(());
+                }
+                match self.content(element) {
+                    Some(content) => f.write_str(&content)?,
+                    None => data.children.iter().try_for_each(|child| child.fmt(f))?,
+                }
+                write!(f, "</{}>", element.name)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TestNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TestNode").field(&self.to_string()).finish()
+    }
+}
+
+impl From<TestNode> for View<TestNode> {
+    fn from(node: TestNode) -> Self {
+        View::from_node(node)
+    }
+}
+
+impl ViewNode for TestNode {
+    fn append_child(&mut self, child: Self) {
+        self.append(child);
+    }
+
+    fn create_dynamic_view<U: Into<View<Self>> + 'static>(
+        mut f: impl FnMut() -> U + 'static,
+    ) -> View<Self> {
+        let group = TestNode::group();
+        let mut content = group.clone();
+        create_effect(move || {
+            content.clear();
+            content.append_view(f().into());
+        });
+        View::from_node(group)
+    }
+}
+
+/// Renders into the in-memory tree the same way the DOM backend renders into the document. Node
+/// refs are not filled in since there is no DOM node to point to.
+impl ViewHtmlNode for TestNode {
+    fn create_element(tag: Cow<'static, str>) -> Self {
+        Self::new(Kind::Element(ElementData {
+            name: tag.into_owned(),
+            ..Default::default()
+        }))
+    }
+
+    fn create_element_ns(_namespace: &'static str, tag: Cow<'static, str>) -> Self {
+        Self::create_element(tag)
+    }
+
+    fn create_text_node(text: Cow<'static, str>) -> Self {
+        Self::new(Kind::Text(text.into_owned()))
+    }
+
+    fn create_marker_node() -> Self {
+        Self::group()
+    }
+
+    fn set_attribute(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>) {
+        let node = self.clone();
+        value.apply(move |value| {
+            node.with_element_mut(|e| set_or_remove(&mut e.attributes, &name, value))
+        });
+    }
+
+    fn set_class(&mut self, name: Cow<'static, str>, value: MaybeDyn<bool>) {
+        let node = self.clone();
+        value.apply(move |enabled| {
+            node.with_element_mut(|e| {
+                e.classes.insert(name.to_string(), enabled);
+            })
+        });
+    }
+
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<String>) {
+        let node = self.clone();
+        value.apply(move |value| {
+            node.with_element_mut(|e| {
+                e.styles.insert(name.to_string(), value);
+            })
+        });
+    }
+
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>) {
+        let node = self.clone();
+        value.apply(move |value| {
+            node.with_element_mut(|e| {
+                e.properties.insert(name.to_string(), value);
+            })
+        });
+    }
+
+    fn set_event_handler(
+        &mut self,
+        name: Cow<'static, str>,
+        handler: impl FnMut(web_sys::Event) + 'static,
+        options: EventOptions,
+    ) {
+        let listener = Listener {
+            id: NEXT_LISTENER_ID.with(|id| id.replace(id.get() + 1)),
+            event: name.into_owned(),
+            handler: Rc::new(RefCell::new(Box::new(handler))),
+            options,
+        };
+        self.with_element_mut(|e| e.listeners.push(listener.clone()));
+        let node = self.clone();
+        on_cleanup(move || node.remove_listener(&listener));
+    }
+
+    fn set_inner_html(&mut self, inner_html: MaybeDyn<String>) {
+        let node = self.clone();
+        inner_html.apply(move |html| node.with_element_mut(|e| e.inner_html = Some(html)));
+    }
+}
+
+/// Set the attribute `name`, or remove it if `value` is `false`.
+fn set_or_remove(
+    attributes: &mut HashMap<String, AttributeValue>,
+    name: &str,
+    value: AttributeValue,
+) {
+    match value {
+        AttributeValue::Bool(false) => attributes.remove(name),
+        value => attributes.insert(name.to_string(), value),
+    };
+}
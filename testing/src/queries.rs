@@ -0,0 +1,136 @@
+//! Queries finding elements the way users find them, modeled after Testing Library.
+//!
+//! Every query comes in four variants:
+//! - `get_by_*` returns the only matching element and panics if there is none or more than one.
+//! - `query_by_*` returns `None` instead of panicking if there is none.
+//! - `get_all_by_*` returns every matching element and panics if there is none.
+//! - `query_all_by_*` returns every matching element.
+//!
+//! Queries search the descendants of the node they are called on, and their panic messages
+//! include the tree that was searched.
+
+use crate::node::TestNode;
+
+macro_rules! queries {
+    ($($name:ident($arg:ident) => $description:literal, $matches:expr;)*) => {
+        ::paste::paste! {
+            impl TestNode {
+                $(
+                    #[doc = concat!("Returns every element ", $description, ".")]
+                    pub fn [<query_all_by_ $name>](&self, $arg: &str) -> Vec<TestNode> {
+                        let matches: fn(&TestNode, &str) -> bool = $matches;
+                        self.descendants()
+                            .into_iter()
+                            .filter(|node| matches(node, $arg))
+                            .collect()
+                    }
+
+                    #[doc = concat!("Returns the element ", $description, ", if any.")]
+                    ///
+                    /// # Panics
+                    /// Panics if more than one element matches.
+                    pub fn [<query_by_ $name>](&self, $arg: &str) -> Option<TestNode> {
+                        let what = format!(concat!("element ", $description), $arg = $arg);
+                        let mut found = self.[<query_all_by_ $name>]($arg);
+                        if found.len() > 1 {
+                            self.fail(&format!("found {} of {what}", found.len()));
+                        }
+                        found.pop()
+                    }
+
+                    #[doc = concat!("Returns the element ", $description, ".")]
+                    ///
+                    /// # Panics
+                    /// Panics if no element or more than one element matches.
+                    pub fn [<get_by_ $name>](&self, $arg: &str) -> TestNode {
+                        let what = format!(concat!("element ", $description), $arg = $arg);
+                        self.[<query_by_ $name>]($arg)
+                            .unwrap_or_else(|| self.fail(&format!("unable to find an {what}")))
+                    }
+
+                    #[doc = concat!("Returns every element ", $description, ".")]
+                    ///
+                    /// # Panics
+                    /// Panics if no element matches.
+                    pub fn [<get_all_by_ $name>](&self, $arg: &str) -> Vec<TestNode> {
+                        let what = format!(concat!("element ", $description), $arg = $arg);
+                        let found = self.[<query_all_by_ $name>]($arg);
+                        if found.is_empty() {
+                            self.fail(&format!("unable to find an {what}"));
+                        }
+                        found
+                    }
+                )*
+            }
+        }
+    };
+}
+
+queries! {
+    text(text) => "whose own text is `{text}`, ignoring surrounding and repeated whitespace",
+        |node, text| {
+            !matches!(node.tag().as_deref(), Some("script" | "style"))
+                && normalize(&node.own_text()) == normalize(text)
+        };
+    role(role) => "with the ARIA role `{role}`, either explicit or implied by its tag",
+        |node, role| self::role(node).as_deref() == Some(role);
+    test_id(id) => "whose `data-testid` is `{id}`",
+        |node, id| node.attribute("data-testid").as_deref() == Some(id);
+}
+
+impl TestNode {
+    fn fail(&self, message: &str) -> ! {
+        panic!("{message} in:\n\n{self:#}")
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the role of `node`: its `role` attribute, or the role implied by its tag.
+fn role(node: &TestNode) -> Option<String> {
+    if let Some(role) = node.attribute("role") {
+        return role.split_whitespace().next().map(str::to_string);
+    }
+    let role = match node.tag()?.as_str() {
+        "a" | "area" if node.attribute("href").is_some() => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" if node.attribute("alt").as_deref() == Some("") => "presentation",
+        "img" => "img",
+        "input" => match node.attribute("type").unwrap_or_default().as_str() {
+            "button" | "image" | "reset" | "submit" => "button",
+            "checkbox" => "checkbox",
+            "radio" => "radio",
+            "range" => "slider",
+            "number" => "spinbutton",
+            "search" => "searchbox",
+            "" | "email" | "tel" | "text" | "url" => "textbox",
+            _ => return None,
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" => "list",
+        "option" => "option",
+        "progress" => "progressbar",
+        "section" => "region",
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
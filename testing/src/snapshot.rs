@@ -0,0 +1,121 @@
+//! Snapshot assertions, see [`assert_snapshot!`](crate::assert_snapshot).
+
+use std::{env, fs, path::Path};
+
+/// Set to `1` to write file snapshots that are missing or do not match instead of failing.
+const UPDATE_VAR: &str = "RSX_UPDATE_SNAPSHOTS";
+
+/// Assert that `value` matches the snapshot, either written inline after `@` or stored in
+/// `tests/snapshots/<name>.snap` in the crate being tested.
+///
+/// The value is printed with `{:#}`, which prints [`TestNode`](crate::TestNode)s and
+/// [`Screen`](crate::Screen)s as indented HTML. Inline snapshots are compared ignoring the
+/// indentation they share and the surrounding blank lines.
+///
+/// Missing or mismatched file snapshots fail the assertion, unless the `RSX_UPDATE_SNAPSHOTS`
+/// environment variable is set to `1`, in which case they are written. New snapshots are thus
+/// created with `RSX_UPDATE_SNAPSHOTS=1 cargo test`, and never written silently on CI.
+///
+/// ```
+/// # use rsx::rsx;
+/// # use rsx_testing::{assert_snapshot, render};
+/// let screen = render(|| rsx!(<ul><li>One</li><li>Two</li></ul>));
+/// assert_snapshot!(screen, @r"
+///     <ul>
+///       <li>
+///         One
+///       </li>
+///       <li>
+///         Two
+///       </li>
+///     </ul>
+/// ");
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($value:expr, @$expected:literal $(,)?) => {
+        $crate::snapshot::assert_inline(&format!("{:#}", $value), $expected)
+    };
+    ($name:literal, $value:expr $(,)?) => {
+        $crate::snapshot::assert_file(env!("CARGO_MANIFEST_DIR"), $name, &format!("{:#}", $value))
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_inline(actual: &str, expected: &str) {
+    let (actual, expected) = (actual.trim_end(), dedent(expected));
+    if actual != expected {
+        panic!("snapshot does not match\n{}", diff(&expected, actual));
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_file(manifest_dir: &str, name: &str, actual: &str) {
+    let path = Path::new(manifest_dir)
+        .join("tests/snapshots")
+        .join(format!("{name}.snap"));
+    let actual = format!("{}\n", actual.trim_end());
+    let update = env::var(UPDATE_VAR).is_ok_and(|value| value == "1");
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {}
+        Ok(expected) if !update => panic!(
+            "snapshot `{name}` does not match, set {UPDATE_VAR}=1 to update it\n{}",
+            diff(expected.trim_end(), actual.trim_end())
+        ),
+        Err(_) if !update => panic!(
+            "snapshot `{name}` does not exist, set {UPDATE_VAR}=1 to write it\n{}",
+            diff("", actual.trim_end())
+        ),
+        _ => {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, actual).unwrap();
+            eprintln!("wrote snapshot {}", path.display());
+        }
+    }
+}
+
+/// Remove the blank lines around `text` and the indentation shared by its lines.
+fn dedent(text: &str) -> String {
+    let lines = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    let lines = &lines[..end.map_or(0, |end| end + 1)];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default().trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Show the lines of `expected` and `actual`, marking the ones that differ.
+fn diff(expected: &str, actual: &str) -> String {
+    let (expected, actual) = (
+        expected.lines().collect::<Vec<_>>(),
+        actual.lines().collect::<Vec<_>>(),
+    );
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => diff += &format!("  {e}\n"),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff += &format!("- {e}\n");
+                }
+                if let Some(a) = a {
+                    diff += &format!("+ {a}\n");
+                }
+            }
+        }
+    }
+    diff
+}
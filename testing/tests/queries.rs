@@ -0,0 +1,253 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use rsx::rsx;
+use rsx_reactive::{root::on_cleanup, signals::create_signal};
+use rsx_testing::{Event, fire_event, fire_input, render};
+use rsx_web::{
+    head::{Meta, Title},
+    transition::TransitionGroup,
+};
+
+#[test]
+fn query_elements() {
+    let screen = render(|| {
+        rsx!(
+            <main>
+                <h1>Posts</h1>
+                <nav><a href="/">Home</a><a>Not a link</a></nav>
+                <ul>
+                    <li data-testid="first">  First   post </li>
+                    <li>Second post</li>
+                </ul>
+                <input type="checkbox"></input>
+                <div role="alert button">Saved</div>
+            </main>
+        )
+    });
+
+    assert_eq!(
+        screen
+            .get_by_text("First post")
+            .attribute("data-testid")
+            .as_deref(),
+        Some("first")
+    );
+    assert_eq!(screen.get_by_role("heading").text_content(), "Posts");
+    assert_eq!(screen.get_by_role("link").text_content(), "Home");
+    assert_eq!(screen.get_by_role("alert").text_content(), "Saved");
+    assert_eq!(
+        screen.get_by_role("checkbox").tag().as_deref(),
+        Some("input")
+    );
+    assert_eq!(screen.get_all_by_role("listitem").len(), 2);
+    assert_eq!(
+        screen
+            .get_by_test_id("first")
+            .parent()
+            .unwrap()
+            .tag()
+            .as_deref(),
+        Some("ul")
+    );
+    assert!(screen.query_by_text("Third post").is_none());
+
+    let list = screen.get_by_role("list");
+    assert!(list.query_by_role("heading").is_none());
+    assert_eq!(
+        list.get_all_by_text("Second post"),
+        [list.children()[1].clone()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "found 2 of element with the ARIA role `listitem`")]
+fn get_by_fails_on_several_matches() {
+    render(|| rsx!(<ul><li>One</li><li>Two</li></ul>)).get_by_role("listitem");
+}
+
+#[test]
+#[should_panic(expected = "unable to find an element whose `data-testid` is `missing` in:\n\n<p>")]
+fn get_by_fails_without_matches() {
+    render(|| rsx!(<p>Text</p>)).get_by_test_id("missing");
+}
+
+fn push<E>(log: &Rc<RefCell<Vec<&'static str>>>, entry: &'static str) -> impl FnMut(E) + use<E> {
+    let log = log.clone();
+    move |_| log.borrow_mut().push(entry)
+}
+
+#[test]
+fn fire_events() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let screen = render({
+        let log = log.clone();
+        move || {
+            rsx!(
+                <section on:click|capture={push(&log, "capture")}>
+                <div onclick={push(&log, "bubble")}>
+                    <form on:submit|prevent={push(&log, "submit")}>
+                        <button onclick={push(&log, "button")}>Send</button>
+                        <button type="button" on:click|stop={push(&log, "stop")}>Cancel</button>
+                    </form>
+                    <span on:focus|once={push(&log, "focus")}>Focus</span>
+                </div>
+                </section>
+            )
+        }
+    });
+
+    assert!(fire_event(&screen.get_by_text("Send"), Event::Click));
+    assert!(fire_event(&screen.get_by_text("Cancel"), Event::Click));
+    assert!(!fire_event(&screen.get_by_role("form"), Event::Submit));
+    fire_event(&screen.get_by_text("Focus"), Event::Focus);
+    fire_event(&screen.get_by_text("Focus"), Event::Focus);
+    assert_eq!(
+        *log.borrow(),
+        [
+            "capture", "button", "bubble", "submit", "capture", "stop", "submit", "focus"
+        ]
+    );
+}
+
+#[test]
+fn bindings_and_dynamic_content() {
+    let cleanups = Rc::new(RefCell::new(0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let screen = render({
+        let cleanups = cleanups.clone();
+        let log = log.clone();
+        move || {
+            let name = create_signal(String::new());
+            let agree = create_signal(false);
+            let cleanups = cleanups.clone();
+            rsx!(
+                <form>
+                    <input bind:value={name} on:input={push(&log, "input")}></input>
+                    <input type="checkbox" bind:checked={agree}></input>
+                    <p>{name.map(|name| format!("Hello {name}"))}</p>
+                    {rsx::Element::Dynamic(Box::new(move || {
+                        let cleanups = cleanups.clone();
+                        on_cleanup(move || *cleanups.borrow_mut() += 1);
+                        agree.get().then(|| rsx!(<button>Continue</button>)).into()
+                    }))}
+                </form>
+            )
+        }
+    });
+
+    fire_event(
+        &screen.get_by_role("textbox"),
+        Event::Input("Ada".to_string()),
+    );
+    assert!(screen.query_by_text("Hello Ada").is_some());
+    // The handler is chained with the binding and gets its own copy of the event.
+    assert_eq!(*log.borrow(), ["input"]);
+
+    assert!(screen.query_by_role("button").is_none());
+    fire_event(&screen.get_by_role("checkbox"), Event::Click);
+    assert_eq!(screen.get_by_role("button").text_content(), "Continue");
+    assert_eq!(*cleanups.borrow(), 1);
+
+    screen.unmount();
+    assert_eq!(*cleanups.borrow(), 2);
+}
+
+#[test]
+fn fill_in_inputs_whose_handlers_use_the_event() {
+    let screen = render(|| {
+        let name = create_signal(String::from("Ada"));
+        rsx!(
+            <div oninput={|event| event.stop_propagation()}>
+                <input bind:value={name} on:input|once={|event| event.prevent_default()}></input>
+                <p>{name.map(|name| format!("Hello {name}"))}</p>
+            </div>
+        )
+    });
+
+    let input = screen.get_by_role("textbox");
+    fire_input(&input, "Grace");
+    assert_eq!(input.to_string(), r#"<input value="Grace">"#);
+    assert!(screen.query_by_text("Hello Grace").is_some());
+    fire_input(&input, "Alan");
+    assert!(screen.query_by_text("Hello Alan").is_some());
+}
+
+#[test]
+fn render_transition_group_items() {
+    let items = Rc::new(RefCell::new(None));
+    let screen = render({
+        let items = items.clone();
+        move || {
+            let list = create_signal(vec!["a", "b"]);
+            *items.borrow_mut() = Some(list);
+            TransitionGroup(
+                "list",
+                Duration::from_millis(200),
+                "ul",
+                move || list.get_clone(),
+                |item| *item,
+                |item| rsx!(<li>{item}</li>),
+            )
+        }
+    });
+    assert_eq!(screen.get_by_role("list").text_content(), "ab");
+
+    // Node references are not filled in here, so the whole list is rendered again.
+    items.borrow().unwrap().set(vec!["c", "a"]);
+    let texts: Vec<_> = screen
+        .get_all_by_role("listitem")
+        .iter()
+        .map(|item| item.text_content())
+        .collect();
+    assert_eq!(texts, ["c", "a"]);
+}
+
+#[test]
+fn collect_head_tags() {
+    let screen = render(|| {
+        let title = create_signal("Home");
+        rsx!(
+            <div>
+                {Title(title)}
+                {Meta(&[("name", "description"), ("content", "Welcome")])}
+                <button onclick={move |_| title.set("Settings")}>Settings</button>
+            </div>
+        )
+    });
+    assert_eq!(
+        screen.head().to_html(),
+        r#"<title>Home</title><meta name="description" content="Welcome">"#
+    );
+
+    fire_event(&screen.get_by_role("button"), Event::Click);
+    assert_eq!(
+        screen.head().to_html(),
+        r#"<title>Settings</title><meta name="description" content="Welcome">"#
+    );
+
+    let head = screen.head().clone();
+    screen.unmount();
+    assert_eq!(head.to_html(), "");
+}
+
+#[test]
+fn collect_scoped_styles_once() {
+    const STYLE: rsx::css::ScopedStyle = rsx::css!("li { margin: 0 }");
+    let screen = render(|| {
+        let items = create_signal(vec![1, 2, 3]);
+        rsx!(
+            <ul>
+                {move || for item in items.get_clone() { <li css={STYLE}>{item}</li> }}
+                <button onclick={move |_| items.set(Vec::new())}>Clear</button>
+            </ul>
+        )
+    });
+    let class = STYLE.class();
+    assert_eq!(
+        screen.head().to_html(),
+        format!(r#"<style data-rsx-scope="{class}">li.{class}{{margin:0}}</style>"#)
+    );
+
+    fire_event(&screen.get_by_role("button"), Event::Click);
+    assert_eq!(screen.head().to_html(), "");
+}
@@ -0,0 +1,82 @@
+use rsx::rsx;
+use rsx_reactive::signals::create_signal;
+use rsx_testing::{Event, assert_snapshot, fire_event, render};
+
+fn todo_list() -> rsx::Element {
+    let done = create_signal(false);
+    rsx!(
+        <section class="todos">
+            <h2 class:done={done}>Groceries</h2>
+            <label><input type="checkbox" bind:checked={done}></input> Done</label>
+            <p dangerously_set_inner_html="<b>Milk</b>"></p>
+            <textarea prop:value="Eggs & bread"></textarea>
+            <img src="/list.png" alt=""></img>
+        </section>
+    )
+}
+
+#[test]
+fn inline_snapshot() {
+    let screen = render(todo_list);
+    fire_event(&screen.get_by_role("checkbox"), Event::Click);
+    assert_snapshot!(screen, @r#"
+        <section class="todos">
+          <h2 class="done">
+            Groceries
+          </h2>
+          <label>
+            <input checked type="checkbox">
+            Done
+          </label>
+          <p>
+            <b>Milk</b>
+          </p>
+          <textarea>
+            Eggs &amp; bread
+          </textarea>
+          <img alt="" src="/list.png">
+        </section>
+    "#);
+    assert_eq!(
+        screen.get_by_role("heading").to_string(),
+        r#"<h2 class="done">Groceries</h2>"#
+    );
+}
+
+#[test]
+fn raw_text_snapshot() {
+    let style = "a > b { color: red; }";
+    let screen = render(|| rsx!(<style>{style}</style>));
+    assert_snapshot!(screen, @r#"
+        <style>
+          a > b { color: red; }
+        </style>
+    "#);
+    assert_eq!(
+        screen.container().to_string(),
+        "<style>a > b { color: red; }</style>"
+    );
+}
+
+#[test]
+fn file_snapshot() {
+    assert_snapshot!("todo_list", render(todo_list));
+}
+
+#[test]
+#[should_panic(expected = "snapshot does not match\n  <p>\n-   Before\n+   After\n  </p>")]
+fn mismatched_snapshot() {
+    assert_snapshot!(render(|| rsx!(<p>After</p>)), @"
+        <p>
+          Before
+        </p>
+    ");
+}
+
+#[test]
+#[should_panic(
+    expected = "snapshot `missing` does not exist, set RSX_UPDATE_SNAPSHOTS=1 to write it"
+)]
+fn missing_snapshot() {
+    assert_snapshot!("missing", render(|| rsx!(<p>New</p>)));
+}
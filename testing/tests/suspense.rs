@@ -0,0 +1,55 @@
+use std::{
+    cell::Cell,
+    future::{pending, poll_fn},
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use rsx::rsx;
+use rsx_reactive::{effects::create_effect, futures::create_resource, signals::create_signal};
+use rsx_testing::{Event, fire_event, render};
+use rsx_web::suspense::Suspense;
+
+#[test]
+fn reveal_children_once() {
+    let waker = Rc::new(Cell::new(None::<Waker>));
+    let done = Rc::new(Cell::new(false));
+    let (task_waker, task_done) = (waker.clone(), done.clone());
+    let screen = render(move || {
+        let (task_waker, task_done) = (task_waker.clone(), task_done.clone());
+        Suspense(
+            || rsx!(<p>Loading</p>),
+            move || {
+                let (task_waker, task_done) = (task_waker.clone(), task_done.clone());
+                let value = create_resource(poll_fn(move |cx| match task_done.get() {
+                    true => Poll::Ready("Loaded"),
+                    false => {
+                        task_waker.set(Some(cx.waker().clone()));
+                        Poll::Pending
+                    }
+                }));
+                let reload = create_signal(false);
+                create_effect(move || {
+                    if reload.get() {
+                        create_resource(pending::<()>());
+                    }
+                });
+                rsx!(
+                    <div>
+                        <p>{value.value().map(|value| value.unwrap_or_default().to_string())}</p>
+                        <button onclick={move |_| reload.set(true)}>Reload</button>
+                    </div>
+                )
+            },
+        )
+    });
+    assert_eq!(screen.text_content(), "Loading");
+
+    done.set(true);
+    waker.take().unwrap().wake();
+    assert_eq!(screen.text_content(), "LoadedReload");
+
+    // The boundary is pending again, but the children stay.
+    fire_event(&screen.get_by_role("button"), Event::Click);
+    assert_eq!(screen.text_content(), "LoadedReload");
+}
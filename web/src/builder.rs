@@ -0,0 +1,189 @@
+//! A macro-free way of building views.
+//!
+//! Every standard HTML element has a constructor function returning an [`ElementBuilder`], whose
+//! methods set attributes, listeners and children before turning it into a [`View`]:
+//!
+//! ```no_run
+//! # use rsx::events::EventHandlersExt;
+//! # use rsx_reactive::signals::create_signal;
+//! # use rsx_web::{builder::*, node::DomNode, view::View};
+//! let count = create_signal(0);
+//! let view: View<DomNode> = div()
+//!     .class("counter")
+//!     .attr("data-id", 1)
+//!     .on_click(move |_| count.set(count.get() + 1))
+//!     .child(span().text(move || count.get()))
+//!     .into();
+//! ```
+//!
+//! Builders work with any [`ViewHtmlNode`] backend. The DOM backend renders [`rsx::Element`]s
+//! through them as well.
+
+use std::borrow::Cow;
+
+use crate::{node::ViewHtmlNode, view::View};
+use rsx::{
+    AttributeValue, EventListener, MaybeDyn, NodeRef,
+    bind::Bound,
+    events::{self, EventDescriptor, EventHandlers},
+};
+
+/// Builds an element of the backend `G`.
+pub struct ElementBuilder<G: ViewHtmlNode> {
+    node: G,
+}
+
+/// Create a builder for an element in the HTML namespace.
+pub fn element<G: ViewHtmlNode>(tag: impl Into<Cow<'static, str>>) -> ElementBuilder<G> {
+    ElementBuilder {
+        node: G::create_element(tag.into()),
+    }
+}
+
+/// Create a builder for an element in `namespace`, e.g. [`SVG`](crate::node::namespace::SVG).
+pub fn element_ns<G: ViewHtmlNode>(
+    namespace: &'static str,
+    tag: impl Into<Cow<'static, str>>,
+) -> ElementBuilder<G> {
+    ElementBuilder {
+        node: G::create_element_ns(namespace, tag.into()),
+    }
+}
+
+impl<G: ViewHtmlNode> ElementBuilder<G> {
+    /// Set an attribute.
+    pub fn attr(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<MaybeDyn<AttributeValue>>,
+    ) -> Self {
+        self.node.set_attribute(name.into(), value.into());
+        self
+    }
+
+    /// Set the `class` attribute.
+    pub fn class(self, value: impl Into<MaybeDyn<AttributeValue>>) -> Self {
+        self.attr("class", value)
+    }
+
+    /// Set the `id` attribute.
+    pub fn id(self, value: impl Into<MaybeDyn<AttributeValue>>) -> Self {
+        self.attr("id", value)
+    }
+
+    /// Add or remove a single class, like `class:<name>={...}` in `rsx!`.
+    pub fn toggle_class(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<MaybeDyn<bool>>,
+    ) -> Self {
+        self.node.set_class(name.into(), value.into());
+        self
+    }
+
+    /// Set a single CSS property, like `style:<name>={...}` in `rsx!`.
+    pub fn style(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<MaybeDyn<String>>,
+    ) -> Self {
+        self.node.set_style_property(name.into(), value.into());
+        self
+    }
+
+    /// Set a DOM property, like `prop:<name>={...}` in `rsx!`.
+    pub fn prop(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<MaybeDyn<AttributeValue>>,
+    ) -> Self {
+        self.node.set_property(name.into(), value.into());
+        self
+    }
+
+    /// Bind a property to a signal, like `bind:<name>={...}` in `rsx!`, e.g.
+    /// `input().bind(rsx::bind::value(name))`.
+    pub fn bind(self, bound: Bound) -> Self {
+        let (name, value) = bound.property;
+        let mut builder = self.prop(name, value);
+        for (event, listener) in bound.listeners {
+            builder = builder.listener(event, listener);
+        }
+        builder
+    }
+
+    /// Attach an untyped event listener.
+    pub fn listener(mut self, name: impl Into<Cow<'static, str>>, listener: EventListener) -> Self {
+        self.node
+            .set_event_handler(name.into(), listener.handler, listener.options);
+        self
+    }
+
+    /// Set the inner HTML of the element. The markup is inserted as is.
+    pub fn inner_html(mut self, value: impl Into<MaybeDyn<String>>) -> Self {
+        self.node.set_inner_html(value.into());
+        self
+    }
+
+    /// Fill `node_ref` with the element, until the current scope is cleaned up.
+    pub fn node_ref(mut self, node_ref: NodeRef) -> Self {
+        self.node.set_node_ref(node_ref);
+        self
+    }
+
+    /// Append a child.
+    pub fn child(mut self, child: impl Into<View<G>>) -> Self {
+        self.node.append_view(child.into());
+        self
+    }
+
+    /// Append a text node, which is updated whenever a signal read by `text` changes.
+    pub fn text(self, text: impl Into<MaybeDyn<String>>) -> Self {
+        match text.into() {
+            MaybeDyn::Static(text) => self.child(G::create_text_node(text.into())),
+            MaybeDyn::Dynamic(f) => {
+                let view = G::create_dynamic_view(move || G::create_text_node(f().into()));
+                self.child(view)
+            }
+        }
+    }
+
+    /// Finish building the element.
+    pub fn build(self) -> View<G> {
+        View::from_node(self.node)
+    }
+}
+
+impl<G: ViewHtmlNode> EventHandlers for ElementBuilder<G> {
+    fn on<E: EventDescriptor>(self, event: E, handler: impl FnMut(E::Event) + 'static) -> Self {
+        self.listener(E::NAME, events::listener(event, handler))
+    }
+}
+
+impl<G: ViewHtmlNode> From<ElementBuilder<G>> for View<G> {
+    fn from(builder: ElementBuilder<G>) -> Self {
+        builder.build()
+    }
+}
+
+macro_rules! define_elements {
+    ($($tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Create a builder for a `<", stringify!($tag), ">` element.")]
+            pub fn $tag<G: ViewHtmlNode>() -> ElementBuilder<G> {
+                element(stringify!($tag))
+            }
+        )*
+    };
+}
+
+define_elements! {
+    a, abbr, address, area, article, aside, audio, b, base, bdi, bdo, blockquote, body, br,
+    button, canvas, caption, cite, code, col, colgroup, data, datalist, dd, del, details, dfn,
+    dialog, div, dl, dt, em, embed, fieldset, figcaption, figure, footer, form, h1, h2, h3, h4, h5,
+    h6, head, header, hgroup, hr, html, i, iframe, img, input, ins, kbd, label, legend, li, link,
+    main, map, mark, menu, meta, meter, nav, noscript, object, ol, optgroup, option, output, p,
+    picture, pre, progress, q, rp, rt, ruby, s, samp, script, search, section, select, slot, small,
+    source, span, strong, style, sub, summary, sup, table, tbody, td, template, textarea, tfoot,
+    th, thead, time, title, tr, track, u, ul, var, video, wbr,
+}
@@ -0,0 +1,372 @@
+//! Managing the tags of the document head.
+//!
+//! [`Title`], [`Meta`], [`Link`] and [`Style`] render nothing where they are used. Instead, they
+//! register a tag with the [`HeadManager`] provided in context, so every page can set its own
+//! title and metadata:
+//!
+//! ```no_run
+//! # use rsx::rsx;
+//! # use rsx_web::head::{Link, Meta, Title};
+//! fn post(title: &str, summary: &str) -> rsx::Element {
+//!     rsx!(
+//!         <article>
+//!             {Title(title)}
+//!             {Meta(&[("name", "description"), ("content", summary)])}
+//!             {Meta(&[("property", "og:title"), ("content", title)])}
+//!             {Link(&[("rel", "canonical"), ("href", "https://example.com/posts/1")])}
+//!             <h1>{title}</h1>
+//!         </article>
+//!     )
+//! }
+//! ```
+//!
+//! Tags that describe the same thing replace each other: there is only one title, one meta tag
+//! per `name`, `property` or `http-equiv`, and one canonical link. The tag registered last wins,
+//! and tags are removed when the scope that registered them is cleaned up, bringing back the
+//! ones they replaced.
+//!
+//! Scoped styles attached with `css={...}` in `rsx!` are added as `<style>` tags in the same way,
+//! once per style, until the last element using it is cleaned up.
+//!
+//! In the browser, [`render_to`](crate::node::render_to) provides a manager that patches the tags
+//! into `document.head` and sets the title with `document.title`. On the server, the tags are
+//! collected by the manager that [`render_to_string_with_head`] provides. Tags registered outside
+//! of a manager are ignored.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Write},
+    rc::Rc,
+};
+
+use rsx::{MaybeDyn, css::ScopedStyle, html::write_escaped};
+use rsx_reactive::{
+    context::{provide_context, try_use_context},
+    root::{create_root, on_cleanup},
+};
+
+use crate::{document, node::ssr_render::with_ssr};
+
+/// A tag registered with a [`HeadManager`].
+struct Tag {
+    id: usize,
+    /// Tags with the same key replace each other.
+    key: Option<String>,
+    name: &'static str,
+    attributes: Vec<(String, String)>,
+    content: String,
+    /// The element patched into `document.head`, if any.
+    node: Option<web_sys::Element>,
+}
+
+#[derive(Default)]
+struct HeadState {
+    /// Registered tags, in the order they were registered.
+    tags: Vec<Tag>,
+    next_id: usize,
+    /// Whether to patch `document.head`, and the title of the document before any [`Title`].
+    dom: Option<String>,
+    /// The tag of every attached scoped style, by class, and the number of elements using it.
+    styles: HashMap<&'static str, (usize, usize)>,
+}
+
+/// Collects the tags registered by [`Title`], [`Meta`], [`Link`] and [`Style`]. It is a cheap
+/// handle, cloning it returns a handle to the same manager.
+#[derive(Clone, Default)]
+pub struct HeadManager(Rc<RefCell<HeadState>>);
+
+thread_local! {
+    /// The manager patching `document.head`, used when no manager is provided.
+    static DOM_HEAD: HeadManager = HeadManager(Rc::new(RefCell::new(HeadState {
+        dom: Some(document().title()),
+        ..Default::default()
+    })));
+}
+
+impl HeadManager {
+    /// Create a manager that collects tags without touching the DOM.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the manager patching `document.head`, shared by every component rendered on this
+    /// thread.
+    pub(crate) fn dom() -> Self {
+        DOM_HEAD.with(Clone::clone)
+    }
+
+    /// Returns the current tags as HTML, in the order they were registered, leaving out tags
+    /// that were replaced.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let state = self.0.borrow();
+        for tag in state.active() {
+            write_tag(&mut html, tag).unwrap();
+        }
+        html
+    }
+
+    fn insert(&self, name: &'static str, attributes: &[(&str, &str)]) -> usize {
+        let mut state = self.0.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<_>>();
+        state.tags.push(Tag {
+            id,
+            key: key(name, &attributes),
+            name,
+            attributes,
+            content: String::new(),
+            node: None,
+        });
+        drop(state);
+        self.sync();
+        id
+    }
+
+    fn set_content(&self, id: usize, content: String) {
+        let mut state = self.0.borrow_mut();
+        if let Some(tag) = state.tags.iter_mut().find(|tag| tag.id == id) {
+            tag.content = content;
+            if let Some(node) = &tag.node {
+                node.set_text_content(Some(&tag.content));
+            }
+        }
+        drop(state);
+        self.sync();
+    }
+
+    fn remove(&self, id: usize) {
+        let mut state = self.0.borrow_mut();
+        if let Some(index) = state.tags.iter().position(|tag| tag.id == id)
+            && let Some(node) = state.tags.remove(index).node
+        {
+            node.remove();
+        }
+        drop(state);
+        self.sync();
+    }
+
+    /// Patch `document.head` to match the active tags, if this manager is the DOM one.
+    fn sync(&self) {
+        let mut state = self.0.borrow_mut();
+        let Some(initial_title) = state.dom.clone() else {
+            return;
+        };
+        let active = state.active().map(|tag| tag.id).collect::<Vec<_>>();
+        let head = document().head().expect("no `<head>` exists");
+        // Nodes of replaced tags, reused by identical tags instead of being re-created, e.g. when
+        // a nested page adds the same meta tag.
+        let mut spare = Vec::new();
+        for tag in &mut state.tags {
+            if !active.contains(&tag.id)
+                && let Some(node) = tag.node.take()
+            {
+                spare.push((
+                    tag.key.clone(),
+                    tag.attributes.clone(),
+                    tag.content.clone(),
+                    node,
+                ));
+            }
+        }
+        let mut title = initial_title;
+        for tag in &mut state.tags {
+            let is_active = active.contains(&tag.id);
+            if tag.name == "title" {
+                if is_active {
+                    title.clone_from(&tag.content);
+                }
+                continue;
+            }
+            if !is_active || tag.node.is_some() {
+                continue;
+            }
+            let reused = spare.iter().position(|(key, attributes, content, _)| {
+                (key, attributes, content) == (&tag.key, &tag.attributes, &tag.content)
+            });
+            tag.node = match reused {
+                Some(index) => Some(spare.remove(index).3),
+                None => {
+                    let node = document().create_element(tag.name).unwrap();
+                    for (name, value) in &tag.attributes {
+                        node.set_attribute(name, value).unwrap();
+                    }
+                    if !tag.content.is_empty() {
+                        node.set_text_content(Some(&tag.content));
+                    }
+                    head.append_child(&node).unwrap();
+                    Some(node)
+                }
+            };
+        }
+        for (_, _, _, node) in spare {
+            node.remove();
+        }
+        if document().title() != title {
+            document().set_title(&title);
+        }
+    }
+}
+
+impl HeadState {
+    /// Returns the tags that were not replaced by a later tag with the same key.
+    fn active(&self) -> impl Iterator<Item = &Tag> {
+        self.tags.iter().enumerate().filter_map(|(i, tag)| {
+            let replaced =
+                tag.key.is_some() && self.tags[i + 1..].iter().any(|later| later.key == tag.key);
+            (!replaced).then_some(tag)
+        })
+    }
+}
+
+/// Returns the key of a tag, or `None` if it never replaces other tags.
+fn key(name: &str, attributes: &[(String, String)]) -> Option<String> {
+    let attribute = |wanted: &str| {
+        attributes
+            .iter()
+            .find(|(name, _)| name == wanted)
+            .map(|(_, value)| value.as_str())
+    };
+    match name {
+        "title" => Some("title".to_string()),
+        "meta" if attribute("charset").is_some() => Some("meta charset".to_string()),
+        "meta" => ["name", "property", "http-equiv"]
+            .iter()
+            .find_map(|name| attribute(name).map(|value| format!("meta {name}={value}"))),
+        "link" if attribute("rel") == Some("canonical") => Some("link canonical".to_string()),
+        "style" => attribute(SCOPE_ATTRIBUTE).map(|class| format!("style {class}")),
+        _ => None,
+    }
+}
+
+fn write_tag(w: &mut impl Write, tag: &Tag) -> fmt::Result {
+    write!(w, "<{}", tag.name)?;
+    for (name, value) in &tag.attributes {
+        write!(w, " {name}=\"")?;
+        write_escaped(w, value)?;
+        w.write_char('"')?;
+    }
+    w.write_char('>')?;
+    match tag.name {
+        "meta" | "link" => return Ok(()),
+        // Stylesheets are trusted and escaping would break them.
+        "style" => w.write_str(&tag.content)?,
+        _ => write_escaped(w, &tag.content)?,
+    }
+    write!(w, "</{}>", tag.name)
+}
+
+/// Register a tag until the current scope is cleaned up.
+fn register(
+    name: &'static str,
+    attributes: &[(&str, &str)],
+    content: MaybeDyn<String>,
+) -> rsx::Element {
+    if let Some(head) = try_use_context::<HeadManager>() {
+        let id = head.insert(name, attributes);
+        let manager = head.clone();
+        content.apply(move |content| manager.set_content(id, content));
+        on_cleanup(move || head.remove(id));
+    }
+    rsx::Element::Fragment(Vec::new())
+}
+
+/// Set the title of the document. The title can be dynamic, e.g. `Title(move || ...)`.
+#[allow(non_snake_case)]
+pub fn Title(title: impl Into<MaybeDyn<String>>) -> rsx::Element {
+    register("title", &[], title.into())
+}
+
+/// Add a `<meta>` tag with `attributes`.
+#[allow(non_snake_case)]
+pub fn Meta(attributes: &[(&str, &str)]) -> rsx::Element {
+    register("meta", attributes, MaybeDyn::Static(String::new()))
+}
+
+/// Add a `<link>` tag with `attributes`.
+#[allow(non_snake_case)]
+pub fn Link(attributes: &[(&str, &str)]) -> rsx::Element {
+    register("link", attributes, MaybeDyn::Static(String::new()))
+}
+
+/// Add a `<style>` tag with the stylesheet `css`.
+#[allow(non_snake_case)]
+pub fn Style(css: impl Into<String>) -> rsx::Element {
+    register("style", &[], MaybeDyn::Static(css.into()))
+}
+
+/// The attribute holding the class of the `<style>` tags added for scoped styles.
+const SCOPE_ATTRIBUTE: &str = "data-rsx-scope";
+
+/// Add the stylesheets of [scoped styles](rsx::css) to the head of the [`HeadManager`] provided in
+/// context. This is called by the render backends of this crate, and by other backends that want
+/// the styles of `css={...}` props to be added.
+pub fn install_style_handler() {
+    rsx::css::set_style_handler(attach_style);
+}
+
+/// Register the stylesheet of `style` until the current scope is cleaned up. It is only registered
+/// once however many elements attach it, and removed when the last of them is cleaned up.
+fn attach_style(style: ScopedStyle) {
+    let Some(head) = try_use_context::<HeadManager>() else {
+        return;
+    };
+    let class = style.class();
+    let attached = head
+        .0
+        .borrow_mut()
+        .styles
+        .get_mut(class)
+        .map(|(_, count)| *count += 1);
+    if attached.is_none() {
+        let id = head.insert("style", &[(SCOPE_ATTRIBUTE, class)]);
+        head.set_content(id, style.css().to_string());
+        head.0.borrow_mut().styles.insert(class, (id, 1));
+    }
+    on_cleanup(move || {
+        let mut state = head.0.borrow_mut();
+        let Some((id, count)) = state.styles.get_mut(class) else {
+            return;
+        };
+        *count -= 1;
+        if *count == 0 {
+            let id = *id;
+            state.styles.remove(class);
+            drop(state);
+            head.remove(id);
+        }
+    });
+}
+
+/// The result of [`render_to_string_with_head`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderedPage {
+    /// The tags registered while rendering, to insert into `<head>`.
+    pub head: String,
+    /// The rendered component.
+    pub body: String,
+}
+
+/// Render a component to an HTML string along with the head tags it registered.
+pub fn render_to_string_with_head<C: Fn() -> rsx::Element>(component: C) -> RenderedPage {
+    let head = HeadManager::new();
+    let mut body = String::new();
+    let root = with_ssr(|| {
+        create_root(|| {
+            provide_context(head.clone());
+            body = component().to_string();
+        })
+    });
+    // Read the head before disposing the root, which removes every tag.
+    let page = RenderedPage {
+        head: head.to_html(),
+        body,
+    };
+    root.dispose();
+    page
+}
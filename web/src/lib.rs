@@ -1,7 +1,16 @@
+pub mod builder;
+pub mod head;
 pub mod macros;
 pub mod node;
+pub mod portal;
+pub mod router;
+#[cfg(feature = "ssg")]
+pub mod ssg;
+pub mod suspense;
+pub mod timers;
+pub mod transition;
 mod utils;
-mod view;
+pub mod view;
 
 /// Utility function for accessing the global [`web_sys::Window`] object.
 pub fn window() -> web_sys::Window {
@@ -0,0 +1,225 @@
+//! Event delegation.
+//!
+//! Instead of attaching one listener per element, delegated handlers are stored on their element
+//! as a JS property and a single listener per event type is installed on the render root. When an
+//! event reaches the root, its composed path is walked from the target upwards and every stored
+//! handler is called until one of them stops propagation.
+//!
+//! While a delegated handler runs, `event.currentTarget` is set to the element it was attached
+//! to, as if it had been attached directly. The root listeners belong to the reactive root of the
+//! render and are removed once it is unmounted.
+//!
+//! Only events that bubble can be delegated. Listeners with the `capture`, `passive` or `once`
+//! modifiers are always attached directly to their element.
+
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+};
+
+use js_sys::Array;
+use rsx_reactive::{node::NodeHandle, root::on_cleanup};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+use crate::node::dom_node::{add_event_listener, listener_closure};
+
+/// A render with event delegation enabled.
+#[derive(Clone)]
+pub(crate) struct DelegationRoot {
+    /// The node that listeners are delegated to.
+    pub node: web_sys::Node,
+    /// The scope of the reactive root of the render, so that the root listeners are removed when
+    /// it is unmounted rather than when the element that needed them is.
+    pub scope: NodeHandle,
+    /// Tells apart the handlers of renders sharing the same node.
+    id: usize,
+    /// The events whose root listener is installed.
+    installed: Rc<RefCell<HashSet<String>>>,
+}
+
+impl DelegationRoot {
+    pub fn new(node: web_sys::Node, scope: NodeHandle) -> Self {
+        thread_local! {
+            static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+        }
+        Self {
+            node,
+            scope,
+            id: NEXT_ID.with(|next| next.replace(next.get() + 1)),
+            installed: Rc::default(),
+        }
+    }
+
+    /// The JS property under which the handlers for `name` are stored on an element.
+    fn handler_key(&self, name: &str) -> String {
+        format!("$$rsx_{}_{name}", self.id)
+    }
+
+    /// Returns `true` the first time it is called with `name`, when its root listener needs to be
+    /// installed.
+    fn install(&self, name: &str) -> bool {
+        self.installed.borrow_mut().insert(name.to_string())
+    }
+}
+
+thread_local! {
+    /// The render that listeners are delegated to while rendering, if delegation is enabled.
+    static DELEGATION_ROOT: RefCell<Option<DelegationRoot>> = const { RefCell::new(None) };
+}
+
+/// Returns the current delegation root, if any.
+pub(crate) fn delegation_root() -> Option<DelegationRoot> {
+    DELEGATION_ROOT.with(|root| root.borrow().clone())
+}
+
+/// Run `f` with `root` as the delegation root, restoring the previous one afterwards.
+pub(crate) fn with_delegation_root<T>(root: Option<DelegationRoot>, f: impl FnOnce() -> T) -> T {
+    let prev = DELEGATION_ROOT.with(|current| current.replace(root));
+    let ret = f();
+    DELEGATION_ROOT.with(|current| current.replace(prev));
+    ret
+}
+
+/// Returns the handlers stored on `target` under `key`, if any.
+fn handlers(target: &JsValue, key: &JsValue) -> Option<Array> {
+    js_sys::Reflect::get(target, key).ok()?.dyn_into().ok()
+}
+
+/// Attach `listener` to `element`, delegating it to the current delegation root if there is one
+/// and the event supports it. Otherwise the listener is attached directly.
+pub(crate) fn attach_listener(
+    element: &web_sys::Element,
+    name: Cow<'static, str>,
+    listener: rsx::EventListener,
+) {
+    let options = listener.options;
+    let delegatable =
+        rsx::events::is_delegated(&name) && !options.capture && !options.passive && !options.once;
+    match delegation_root() {
+        Some(root) if delegatable => delegate(&root, element, name, listener),
+        _ => add_event_listener(element, name, listener),
+    }
+}
+
+/// Store `listener` on `element` and make sure `root` dispatches `name` events to it.
+///
+/// An element can have several handlers for the same event, e.g. a `bind:value` directive and an
+/// `on:input` handler, so they are stored in an array. Removing one replaces the array, which
+/// lets an event being dispatched finish with the handlers it started with.
+fn delegate(
+    root: &DelegationRoot,
+    element: &web_sys::Element,
+    name: Cow<'static, str>,
+    listener: rsx::EventListener,
+) {
+    install_root_listener(root, &name);
+
+    let (cb, _) = listener_closure(listener);
+    let key = JsValue::from_str(&root.handler_key(&name));
+    let stored = handlers(element, &key).unwrap_or_default();
+    stored.push(cb.as_ref());
+    js_sys::Reflect::set(element, &key, &stored).unwrap();
+
+    let element = element.clone();
+    on_cleanup(move || {
+        let remaining = handlers(&element, &key)
+            .unwrap_or_default()
+            .iter()
+            .filter(|handler| handler != cb.as_ref())
+            .collect::<Array>();
+        match remaining.length() {
+            0 => js_sys::Reflect::delete_property(&element, &key).unwrap(),
+            _ => js_sys::Reflect::set(&element, &key, &remaining).unwrap(),
+        };
+        drop(cb);
+    });
+}
+
+/// Install the listener dispatching `name` events on the node of `root` if it is not installed
+/// yet.
+fn install_root_listener(root: &DelegationRoot, name: &str) {
+    if !root.install(name) {
+        return;
+    }
+
+    let key = JsValue::from_str(&root.handler_key(name));
+    let dispatch_root = root.node.clone();
+    let dispatch = move |event: web_sys::Event| {
+        for target in event.composed_path().iter() {
+            if let Some(handlers) = handlers(&target, &key) {
+                set_current_target(&event, &target);
+                for handler in handlers.iter() {
+                    handler
+                        .unchecked_ref::<js_sys::Function>()
+                        .call1(&target, &event)
+                        .unwrap();
+                }
+                if event.cancel_bubble() {
+                    break;
+                }
+            }
+            if AsRef::<JsValue>::as_ref(&dispatch_root) == &target {
+                break;
+            }
+        }
+        // Fall back to the `currentTarget` of the prototype, i.e. the root.
+        js_sys::Reflect::delete_property(&event, &JsValue::from_str("currentTarget")).unwrap();
+    };
+
+    // The root listener must outlive the scope of the element that caused it to be installed.
+    root.scope.run_in(|| {
+        add_event_listener(
+            &root.node,
+            name.to_string().into(),
+            rsx::EventListener {
+                handler: Box::new(dispatch),
+                options: Default::default(),
+            },
+        );
+    });
+}
+
+/// Shadow the `currentTarget` of `event` with `target`.
+fn set_current_target(event: &web_sys::Event, target: &JsValue) {
+    let descriptor = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &descriptor,
+        &JsValue::from_str("configurable"),
+        &JsValue::TRUE,
+    )
+    .unwrap();
+    js_sys::Reflect::set(&descriptor, &JsValue::from_str("value"), target).unwrap();
+    js_sys::Object::define_property(event, &JsValue::from_str("currentTarget"), &descriptor);
+}
+
+#[cfg(test)]
+mod tests {
+    use rsx_reactive::root::{create_root, use_current_scope};
+
+    use super::*;
+
+    /// A delegation root on a node that is never used by JS.
+    fn root() -> DelegationRoot {
+        let mut root = None;
+        let _ = create_root(|| {
+            root = Some(DelegationRoot::new(
+                JsValue::UNDEFINED.unchecked_into(),
+                use_current_scope(),
+            ))
+        });
+        root.unwrap()
+    }
+
+    #[test]
+    fn renders_sharing_a_node_are_kept_apart() {
+        let (first, second) = (root(), root());
+        assert_ne!(first.handler_key("click"), second.handler_key("click"));
+
+        assert!(first.install("click"));
+        assert!(!first.install("click"));
+        // The second render installs its own listener, removed with its own scope.
+        assert!(second.install("click"));
+    }
+}
@@ -3,13 +3,14 @@ use std::{
     borrow::Cow,
 };
 
+use rsx::{AttributeValue, MaybeDyn, NodeRef, events::EventOptions};
 use rsx_reactive::{effects::create_effect_initial, root::on_cleanup};
-use web_sys::wasm_bindgen::{JsCast, intern, prelude::Closure};
+use web_sys::wasm_bindgen::{JsCast, JsValue, intern, prelude::Closure};
 
 use crate::{
     document,
-    node::ViewHtmlNode,
-    utils,
+    node::{ViewHtmlNode, WebSysNode, delegation::attach_listener, namespace},
+    transition, utils,
     view::{View, ViewNode},
 };
 
@@ -37,7 +38,7 @@ impl ViewNode for DomNode {
 }
 
 /// Internal implementation that is shared between `DomNode` and `HydrateNode`.
-pub(crate) fn _create_dynamic_view<T: ViewHtmlNode, U: Into<View<T>> + 'static>(
+pub(crate) fn _create_dynamic_view<T: WebSysNode, U: Into<View<T>> + 'static>(
     mut f: impl FnMut() -> U + 'static,
 ) -> View<T> {
     // If `view` is just a single text node, we can just return this node and set up an
@@ -76,7 +77,10 @@ pub(crate) fn _create_dynamic_view<T: ViewHtmlNode, U: Into<View<T>> + 'static>(
                         // Clear all the old nodes away.
                         let old = utils::get_nodes_between(&start_node, &end_node);
                         for node in old {
-                            parent.remove_child(&node).unwrap();
+                            // Nodes with a leave animation remove themselves once it is done.
+                            if !transition::start_leave(&node) {
+                                parent.remove_child(&node).unwrap();
+                            }
                         }
                         // Insert the new nodes in their place.
                         for node in new.nodes {
@@ -97,6 +101,63 @@ pub(crate) fn _create_dynamic_view<T: ViewHtmlNode, U: Into<View<T>> + 'static>(
     }
 }
 
+/// Wrap the handler of `listener` into a JS closure that applies the `prevent_default` and
+/// `stop_propagation` options before running it.
+pub(crate) fn listener_closure(
+    listener: rsx::EventListener,
+) -> (Closure<dyn FnMut(web_sys::Event)>, EventOptions) {
+    let rsx::EventListener {
+        mut handler,
+        options,
+    } = listener;
+    let cb = Closure::wrap(Box::new(move |event: web_sys::Event| {
+        if options.prevent_default {
+            event.prevent_default();
+        }
+        if options.stop_propagation {
+            event.stop_propagation();
+        }
+        handler(event);
+    }) as Box<dyn FnMut(_)>);
+    (cb, options)
+}
+
+/// Attach `listener` to `target` with its [`EventOptions`].
+///
+/// The listener is removed and its closure dropped when the current scope is cleaned up.
+pub(crate) fn add_event_listener(
+    target: &web_sys::EventTarget,
+    name: Cow<'static, str>,
+    listener: rsx::EventListener,
+) {
+    let (cb, options) = listener_closure(listener);
+
+    let js_options = web_sys::AddEventListenerOptions::new();
+    js_options.set_capture(options.capture);
+    js_options.set_passive(options.passive);
+    js_options.set_once(options.once);
+    target
+        .add_event_listener_with_callback_and_add_event_listener_options(
+            intern(&name),
+            cb.as_ref().unchecked_ref(),
+            &js_options,
+        )
+        .unwrap();
+
+    let target = target.clone();
+    on_cleanup(move || {
+        // Listeners are identified by their callback and capture flag only.
+        target
+            .remove_event_listener_with_callback_and_bool(
+                &name,
+                cb.as_ref().unchecked_ref(),
+                options.capture,
+            )
+            .unwrap();
+        drop(cb);
+    });
+}
+
 impl ViewHtmlNode for DomNode {
     fn create_element(tag: Cow<'static, str>) -> Self {
         Self {
@@ -125,77 +186,82 @@ impl ViewHtmlNode for DomNode {
         }
     }
 
-    // fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute) {
-    //     // FIXME: use setAttributeNS if SVG
-    //     if let Some(value) = value.as_static() {
-    //         if let Some(value) = value {
-    //             self.raw
-    //                 .unchecked_ref::<web_sys::Element>()
-    //                 .set_attribute(&name, value)
-    //                 .unwrap();
-    //         }
-    //     } else {
-    //         let node = self.raw.clone().unchecked_into::<web_sys::Element>();
-    //         create_effect(move || match value.get_clone() {
-    //             Some(value) => node.set_attribute(&name, &value).unwrap(),
-    //             None => node.remove_attribute(&name).unwrap(),
-    //         });
-    //     }
-    // }
-
-    // fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
-    //     // FIXME: use setAttributeNS if SVG
-    //     if let Some(value) = value.as_static() {
-    //         if *value {
-    //             self.raw
-    //                 .unchecked_ref::<web_sys::Element>()
-    //                 .set_attribute(&name, "")
-    //                 .unwrap();
-    //         }
-    //     } else {
-    //         let node = self.raw.clone().unchecked_into::<web_sys::Element>();
-    //         create_effect(move || {
-    //             if value.get() {
-    //                 node.set_attribute(&name, "").unwrap();
-    //             } else {
-    //                 node.remove_attribute(&name).unwrap();
-    //             }
-    //         });
-    //     }
-    // }
-
-    // fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>) {
-    //     if let Some(value) = value.as_static() {
-    //         assert!(js_sys::Reflect::set(&self.raw, &name.as_ref().into(), value).unwrap_throw())
-    //     } else {
-    //         let node = self.raw.clone().unchecked_into::<web_sys::Element>();
-    //         create_effect(move || {
-    //             assert!(
-    //                 js_sys::Reflect::set(&node, &name.as_ref().into(), &value.get_clone())
-    //                     .unwrap_throw()
-    //             )
-    //         });
-    //     }
-    // }
+    fn set_attribute(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>) {
+        let element = self.raw.clone().unchecked_into::<web_sys::Element>();
+        let namespace = namespace::attribute(&name);
+        value.apply(move |value| match (value, namespace) {
+            (AttributeValue::String(value), None) => element.set_attribute(&name, &value).unwrap(),
+            (AttributeValue::String(value), Some(namespace)) => element
+                .set_attribute_ns(Some(namespace), &name, &value)
+                .unwrap(),
+            (AttributeValue::Bool(true), None) => element.set_attribute(&name, "").unwrap(),
+            (AttributeValue::Bool(true), Some(namespace)) => element
+                .set_attribute_ns(Some(namespace), &name, "")
+                .unwrap(),
+            (AttributeValue::Bool(false), None) => element.remove_attribute(&name).unwrap(),
+            (AttributeValue::Bool(false), Some(namespace)) => {
+                // Namespaced attributes are removed by their local name.
+                let local_name = name.split_once(':').map_or(name.as_ref(), |(_, name)| name);
+                element
+                    .remove_attribute_ns(Some(namespace), local_name)
+                    .unwrap();
+            }
+        });
+    }
+
+    fn set_class(&mut self, name: Cow<'static, str>, value: MaybeDyn<bool>) {
+        let class_list = self.raw.unchecked_ref::<web_sys::Element>().class_list();
+        value.apply(move |value| {
+            class_list.toggle_with_force(&name, value).unwrap();
+        });
+    }
+
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<String>) {
+        // `style` is read structurally, so this works for SVG and MathML elements too.
+        let style = self.raw.unchecked_ref::<web_sys::HtmlElement>().style();
+        value.apply(move |value| style.set_property(&name, &value).unwrap());
+    }
+
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>) {
+        let node = self.raw.clone();
+        let name = JsValue::from_str(&name);
+        value.apply(move |value| {
+            let value = match value {
+                AttributeValue::String(value) => JsValue::from_str(&value),
+                AttributeValue::Bool(value) => JsValue::from_bool(value),
+            };
+            js_sys::Reflect::set(&node, &name, &value).unwrap();
+        });
+    }
 
     fn set_event_handler(
         &mut self,
         name: Cow<'static, str>,
         handler: impl FnMut(web_sys::Event) + 'static,
+        options: EventOptions,
     ) {
-        let cb = Closure::wrap(Box::new(handler) as Box<dyn FnMut(_)>);
-        self.raw
-            .add_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
-            .unwrap();
-        on_cleanup(|| drop(cb));
+        attach_listener(
+            self.raw.unchecked_ref(),
+            name,
+            rsx::EventListener {
+                handler: Box::new(handler),
+                options,
+            },
+        );
     }
 
-    fn set_inner_html(&mut self, inner_html: Cow<'static, str>) {
-        self.raw
-            .unchecked_ref::<web_sys::Element>()
-            .set_inner_html(&inner_html);
+    fn set_inner_html(&mut self, inner_html: MaybeDyn<String>) {
+        let element = self.raw.clone().unchecked_into::<web_sys::Element>();
+        inner_html.apply(move |html| element.set_inner_html(&html));
     }
 
+    fn set_node_ref(&mut self, node_ref: NodeRef) {
+        node_ref.set(Some(self.raw.clone()));
+        on_cleanup(move || node_ref.set(None));
+    }
+}
+
+impl WebSysNode for DomNode {
     fn as_web_sys(&self) -> &web_sys::Node {
         &self.raw
     }
@@ -1,48 +1,80 @@
-use rsx_reactive::root::create_root;
-use web_sys::wasm_bindgen::{JsCast, intern, prelude::Closure};
+use rsx_reactive::{
+    context::{provide_context, try_use_context},
+    root::{RootHandle, create_root, use_current_scope},
+};
 
-use crate::{document, node::dom_node::DomNode};
+use crate::{
+    head::HeadManager,
+    node::{
+        delegation::{DelegationRoot, with_delegation_root},
+        dom_node::DomNode,
+    },
+    utils,
+    view::View,
+};
 
-pub fn render_to<C: Fn() -> rsx::Element>(component: C, parent: &web_sys::Node) {
-    // Do not call the destructor function, effectively leaking the scope.
-    let _ = create_root(|| render_in_scope(component, parent));
+/// A handle to a component rendered with [`render_to`] or [`render_to_delegated`].
+#[must_use = "dropping the handle keeps the component mounted forever, call `unmount` to remove it"]
+pub struct RenderHandle {
+    root: RootHandle,
+    nodes: Vec<web_sys::Node>,
 }
 
-impl From<rsx::Element> for DomNode {
-    fn from(value: rsx::Element) -> Self {
-        match value {
-            rsx::Element::HTMLElement(element) => {
-                let el = document().create_element(intern(&element.name)).unwrap();
-
-                // Attributes
-                for (name, value) in element.attributes {
-                    el.set_attribute(&name, &value).unwrap();
-                }
-
-                for (name, listener) in element.event_listeners {
-                    let closure = Closure::wrap(listener);
-                    el.add_event_listener_with_callback(&name, closure.as_ref().unchecked_ref())
-                        .unwrap();
-                    closure.forget();
-                }
-
-                // Children (recursive!)
-                for child in element.children {
-                    let child_node = DomNode::from(child);
-                    el.append_child(&child_node.raw).unwrap();
-                }
+impl RenderHandle {
+    /// Dispose the reactive root of the rendered component, running every cleanup callback
+    /// (which removes its event listeners), and remove the rendered nodes from the parent.
+    pub fn unmount(self) {
+        self.root.dispose();
+        utils::remove_view_nodes(&self.nodes);
+    }
+}
 
-                Self { raw: el.into() }
-            }
+/// Render a component into `parent`.
+///
+/// The returned [`RenderHandle`] can be used to unmount the component. If it is dropped instead,
+/// the component stays mounted for the rest of the program.
+pub fn render_to<C: Fn() -> rsx::Element>(component: C, parent: &web_sys::Node) -> RenderHandle {
+    let mut nodes = Vec::new();
+    let root = create_root(|| nodes = render_in_scope(component, parent));
+    RenderHandle { root, nodes }
+}
 
-            rsx::Element::Text(text) => Self {
-                raw: document().create_text_node(&text).into(),
-            },
-        }
-    }
+/// Same as [`render_to`] but with event delegation enabled.
+///
+/// Handlers for bubbling events are not attached to their element. Instead, a single listener
+/// per event type is installed on `parent`, cutting down the number of listeners for large
+/// lists.
+pub fn render_to_delegated<C: Fn() -> rsx::Element>(
+    component: C,
+    parent: &web_sys::Node,
+) -> RenderHandle {
+    let mut nodes = Vec::new();
+    let root = create_root(|| {
+        let root = DelegationRoot::new(parent.clone(), use_current_scope());
+        nodes = with_delegation_root(Some(root), || render_in_scope(component, parent))
+    });
+    RenderHandle { root, nodes }
 }
 
-pub fn render_in_scope<C: Fn() -> rsx::Element>(component: C, parent: &web_sys::Node) {
-    let root = DomNode::from(component());
-    parent.append_child(&root.raw).unwrap();
+/// Render a component into `parent` inside the current reactive scope. Returns the nodes that
+/// were appended to `parent`.
+///
+/// Unless a [`HeadManager`] is already provided, the component's [head tags](crate::head) are
+/// patched into `document.head`.
+pub fn render_in_scope<C: Fn() -> rsx::Element>(
+    component: C,
+    parent: &web_sys::Node,
+) -> Vec<web_sys::Node> {
+    if try_use_context::<HeadManager>().is_none() {
+        provide_context(HeadManager::dom());
+    }
+    crate::head::install_style_handler();
+    crate::transition::install_frame_scheduler();
+    crate::timers::install_clock();
+    let view = View::<DomNode>::from(component());
+    let nodes = view.as_web_sys();
+    for node in &nodes {
+        parent.append_child(node).unwrap();
+    }
+    nodes
 }
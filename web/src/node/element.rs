@@ -0,0 +1,81 @@
+//! Rendering [`rsx::Element`]s with any [`ViewHtmlNode`] backend.
+
+use crate::{
+    builder,
+    node::{
+        ViewHtmlNode,
+        delegation::{delegation_root, with_delegation_root},
+        namespace,
+    },
+    view::View,
+};
+
+impl<G: ViewHtmlNode> From<rsx::Element> for View<G> {
+    fn from(value: rsx::Element) -> Self {
+        render_element(value, None)
+    }
+}
+
+/// Render `element` with `namespace` as the namespace of the enclosing element.
+fn render_element<G: ViewHtmlNode>(
+    element: rsx::Element,
+    namespace: Option<&'static str>,
+) -> View<G> {
+    match element {
+        rsx::Element::HTMLElement(element) => {
+            let ns = namespace::element(&element.name, namespace);
+            let children_ns = namespace::children(&element.name, ns);
+            let mut builder = match ns {
+                Some(ns) => builder::element_ns(ns, element.name),
+                None => builder::element(element.name),
+            };
+
+            for (name, value) in element.attributes {
+                builder = builder.attr(name, value);
+            }
+            // Directives are applied after the attributes so that they take precedence.
+            for (name, enabled) in element.classes {
+                builder = builder.toggle_class(name, enabled);
+            }
+            for (name, value) in element.styles {
+                builder = builder.style(name, value);
+            }
+            for (name, value) in element.properties {
+                builder = builder.prop(name, value);
+            }
+            for (name, listener) in element.event_listeners {
+                builder = builder.listener(name, listener);
+            }
+            if let Some(node_ref) = element.node_ref {
+                builder = builder.node_ref(node_ref);
+            }
+            if let Some(inner_html) = element.inner_html {
+                builder = builder.inner_html(inner_html);
+            }
+
+            // Children (recursive!)
+            for child in element.children {
+                builder = builder.child(render_element(child, children_ns));
+            }
+
+            builder.build()
+        }
+
+        rsx::Element::Text(text) => View::from_node(G::create_text_node(text.into())),
+
+        rsx::Element::Fragment(children) => View::from(
+            children
+                .into_iter()
+                .map(|child| render_element(child, namespace))
+                .collect::<Vec<_>>(),
+        ),
+
+        rsx::Element::Dynamic(f) => {
+            // The view is re-rendered later on, after the delegation root has been reset.
+            let root = delegation_root();
+            G::create_dynamic_view(move || {
+                with_delegation_root(root.clone(), || render_element(f(), namespace))
+            })
+        }
+    }
+}
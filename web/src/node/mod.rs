@@ -1,10 +1,20 @@
 use std::borrow::Cow;
 
+use rsx::{AttributeValue, MaybeDyn, NodeRef, events::EventOptions};
+
 use crate::view::ViewNode;
 
-mod dom_node;
+pub(crate) mod delegation;
+pub(crate) mod dom_node;
 pub mod dom_render;
-pub use dom_render::render_to;
+mod element;
+pub mod namespace;
+pub mod ssr_render;
+pub mod stream_render;
+pub use dom_node::DomNode;
+pub use dom_render::{RenderHandle, render_to, render_to_delegated};
+pub use ssr_render::{is_ssr, render_to_string};
+pub use stream_render::{RenderStream, render_to_stream};
 
 /// A trait that should be implemented for anything that represents an HTML node.
 pub trait ViewHtmlNode: ViewNode {
@@ -21,21 +31,31 @@ pub trait ViewHtmlNode: ViewNode {
     /// Create a new HTML marker (comment) node.
     fn create_marker_node() -> Self;
 
-    /// Set an HTML attribute.
-    // fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute);
-    /// Set a boolean HTML attribute.
-    // fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute);
+    /// Set an HTML attribute. Prefixed attributes such as `xlink:href` are set in their namespace.
+    fn set_attribute(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>);
+    /// Add or remove a class on an element, depending on `value`.
+    fn set_class(&mut self, name: Cow<'static, str>, value: MaybeDyn<bool>);
+    /// Set a CSS property on an element.
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<String>);
     /// Set a JS property on an element.
-    // fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>);
-    /// Set an event handler on an element.
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<AttributeValue>);
+    /// Set an event handler on an element. The handler is removed when the current scope is
+    /// cleaned up.
     fn set_event_handler(
         &mut self,
         name: Cow<'static, str>,
         handler: impl FnMut(web_sys::Event) + 'static,
+        options: EventOptions,
     );
     /// Set the inner HTML value of an element.
-    fn set_inner_html(&mut self, inner_html: Cow<'static, str>);
+    fn set_inner_html(&mut self, inner_html: MaybeDyn<String>);
+    /// Fill `node_ref` with the element until the current scope is cleaned up. Backends whose
+    /// nodes are not web-sys nodes leave it empty.
+    fn set_node_ref(&mut self, _node_ref: NodeRef) {}
+}
 
+/// An HTML node backed by a web-sys node, such as [`DomNode`].
+pub trait WebSysNode: ViewHtmlNode {
     /// Return the raw web-sys node.
     fn as_web_sys(&self) -> &web_sys::Node;
     /// Wrap a raw web-sys node.
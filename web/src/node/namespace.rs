@@ -0,0 +1,39 @@
+//! XML namespaces of elements and attributes.
+//!
+//! Elements are created in the HTML namespace unless they are inside `<svg>` or `<math>`.
+//! `<foreignObject>` switches back to HTML for its children.
+
+pub const SVG: &str = "http://www.w3.org/2000/svg";
+pub const MATHML: &str = "http://www.w3.org/1998/Math/MathML";
+pub const XLINK: &str = "http://www.w3.org/1999/xlink";
+pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+pub const XMLNS: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Returns the namespace of the element `name`, given the namespace of its children's parent.
+/// `None` is the HTML namespace.
+pub fn element(name: &str, parent: Option<&'static str>) -> Option<&'static str> {
+    match name {
+        "svg" => Some(SVG),
+        "math" => Some(MATHML),
+        _ => parent,
+    }
+}
+
+/// Returns the namespace of the children of the element `name` in `namespace`.
+pub fn children(name: &str, namespace: Option<&'static str>) -> Option<&'static str> {
+    match name {
+        "foreignObject" if namespace == Some(SVG) => None,
+        _ => namespace,
+    }
+}
+
+/// Returns the namespace of a prefixed attribute such as `xlink:href`, or `None` if it has none.
+pub fn attribute(name: &str) -> Option<&'static str> {
+    match name.split_once(':') {
+        Some(("xlink", _)) => Some(XLINK),
+        Some(("xml", _)) => Some(XML),
+        Some(("xmlns", _)) => Some(XMLNS),
+        _ if name == "xmlns" => Some(XMLNS),
+        _ => None,
+    }
+}
@@ -0,0 +1,46 @@
+use std::cell::Cell;
+
+use rsx_reactive::root::create_root;
+
+thread_local! {
+    /// Whether we are currently rendering to a string.
+    static IS_SSR: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` while a component is being rendered with [`render_to_string`] or
+/// [`render_to_stream`](super::render_to_stream).
+///
+/// Components that touch the DOM directly (e.g. [`Portal`](crate::portal::Portal)) use this to
+/// skip that work on the server.
+pub fn is_ssr() -> bool {
+    IS_SSR.with(Cell::get)
+}
+
+/// Render a component to an HTML string.
+///
+/// The component is run inside its own reactive root which is disposed once rendering is done.
+/// The markup is produced by the [`Display`](std::fmt::Display) implementation of
+/// [`rsx::Element`], see [`rsx::html`].
+pub fn render_to_string<C: Fn() -> rsx::Element>(component: C) -> String {
+    let mut html = String::new();
+    // Dynamic elements are only evaluated once since nothing can update them on the server.
+    let root = with_ssr(|| create_root(|| html = component().to_string()));
+    root.dispose();
+    html
+}
+
+/// Run `f` as if rendering to a string, so that [`is_ssr`] returns `true`.
+pub(crate) fn with_ssr<T>(f: impl FnOnce() -> T) -> T {
+    /// Restores the previous value, even if `f` panics.
+    struct Reset(bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IS_SSR.with(|ssr| ssr.set(self.0));
+        }
+    }
+
+    let _reset = Reset(IS_SSR.with(|ssr| ssr.replace(true)));
+    crate::head::install_style_handler();
+    f()
+}
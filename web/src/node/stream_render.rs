@@ -0,0 +1,216 @@
+//! Rendering to a stream of HTML chunks.
+//!
+//! The first chunk is the shell: the whole page, with the fallback of every [`Suspense`]
+//! boundary that is still waiting for resources between placeholder markers. Every later chunk is
+//! the content of one boundary, sent as soon as its resources complete and in whatever order
+//! they do. It is wrapped in a `<template>` followed by an inline script replacing the fallback
+//! with it, so the page is usable before everything has loaded.
+//!
+//! [`Suspense`]: crate::suspense::Suspense
+
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+use rsx::rsx;
+use rsx_reactive::{
+    effects::create_effect,
+    futures::{SuspenseState, run_until_stalled},
+    root::{RootHandle, create_root},
+};
+
+use crate::node::ssr_render::with_ssr;
+
+/// Replaces the fallback between the markers `rsx-suspense-<id>` and `rsx-suspense-end-<id>` with
+/// the content of template `rsx-resolved-<id>`. Sent once, before the first boundary.
+const SWAP_SCRIPT: &str = "<script>function $rsx(i){var d=document,\
+     t=d.getElementById(\"rsx-resolved-\"+i),s=d.getElementById(\"rsx-suspense-\"+i),\
+     e=d.getElementById(\"rsx-suspense-end-\"+i);while(s.nextSibling!=e)s.nextSibling.remove();\
+     s.remove();e.replaceWith(t.content);t.remove()}</script>";
+
+/// A suspense boundary waiting for its resources.
+struct Boundary {
+    id: usize,
+    state: SuspenseState,
+    children: rsx::Element,
+}
+
+#[derive(Default)]
+struct StreamState {
+    boundaries: Vec<Boundary>,
+    next_id: usize,
+    /// Woken when a boundary stops waiting.
+    waker: Rc<Cell<Option<Waker>>>,
+}
+
+thread_local! {
+    /// The stream being rendered, if any.
+    static STREAM: RefCell<Option<StreamState>> = const { RefCell::new(None) };
+}
+
+/// Returns `true` while a stream is being rendered.
+pub(crate) fn is_streaming() -> bool {
+    STREAM.with(|stream| stream.borrow().is_some())
+}
+
+/// Defer rendering `children` to a later chunk of the stream being rendered, once `state` is no
+/// longer pending. Returns the placeholder to render in the meantime: `fallback` between two
+/// empty `<template>` markers.
+pub(crate) fn suspend(
+    state: SuspenseState,
+    children: rsx::Element,
+    fallback: rsx::Element,
+) -> rsx::Element {
+    let (id, waker) = STREAM.with(|stream| {
+        let mut stream = stream.borrow_mut();
+        let stream = stream.as_mut().expect("no stream is being rendered");
+        let id = stream.next_id;
+        stream.next_id += 1;
+        stream.boundaries.push(Boundary {
+            id,
+            state,
+            children,
+        });
+        (id, stream.waker.clone())
+    });
+    create_effect(move || {
+        if !state.is_pending()
+            && let Some(waker) = waker.take()
+        {
+            waker.wake();
+        }
+    });
+    // Empty templates are allowed anywhere, including inside `<p>`, `<ul>` or `<table>`, where
+    // the parser would move or close a wrapping element.
+    let start = format!("rsx-suspense-{id}");
+    let end = format!("rsx-suspense-end-{id}");
+    rsx!(<template id={start}></template>{fallback}<template id={end}></template>)
+}
+
+/// Replace the fallback of boundary `id` in `html`, along with its markers, with `content`, as
+/// the swap script does in the browser. Returns `false` if the boundary is not in `html`.
+#[cfg(feature = "ssg")]
+pub(crate) fn splice(html: &mut String, id: usize, content: &str) -> bool {
+    let start = format!("<template id=\"rsx-suspense-{id}\"></template>");
+    let end = format!("<template id=\"rsx-suspense-end-{id}\"></template>");
+    let Some(from) = html.find(&start) else {
+        return false;
+    };
+    let Some(to) = html[from..].find(&end) else {
+        return false;
+    };
+    html.replace_range(from..from + to + end.len(), content);
+    true
+}
+
+/// A stream of HTML chunks, created by [`render_to_stream`].
+///
+/// Polling the stream also polls the resources of the page, so they can be woken from any
+/// thread, e.g. by the runtime of an HTTP server, as long as the stream is polled on the thread
+/// it was created on.
+pub struct RenderStream {
+    root: RootHandle,
+    shell: Option<String>,
+    state: StreamState,
+    sent_script: bool,
+}
+
+/// Render a component to a stream of HTML chunks, flushing the shell right away and the content
+/// of each [`Suspense`](crate::suspense::Suspense) boundary once its resources have completed.
+///
+/// The component is run inside its own reactive root, which is disposed once the stream is
+/// dropped.
+pub fn render_to_stream<C: Fn() -> rsx::Element>(component: C) -> RenderStream {
+    let mut state = Some(StreamState::default());
+    let mut shell = String::new();
+    let root = with_stream(&mut state, || {
+        create_root(|| shell = component().to_string())
+    });
+    RenderStream {
+        root,
+        shell: Some(shell),
+        state: state.unwrap(),
+        sent_script: false,
+    }
+}
+
+/// Run `f` with `state` as the stream being rendered.
+fn with_stream<T>(state: &mut Option<StreamState>, f: impl FnOnce() -> T) -> T {
+    /// Takes the state back, even if `f` panics.
+    struct Reset<'a>(&'a mut Option<StreamState>);
+
+    impl Drop for Reset<'_> {
+        fn drop(&mut self) {
+            *self.0 = STREAM.with(|stream| stream.take());
+        }
+    }
+
+    STREAM.with(|stream| stream.replace(state.take()));
+    let _reset = Reset(state);
+    with_ssr(f)
+}
+
+impl Stream for RenderStream {
+    type Item = String;
+
+    /// Poll for the next chunk. Returns `None` once every boundary has been sent.
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        if let Some(shell) = self.shell.take() {
+            return Poll::Ready(Some(shell));
+        }
+        let Some((id, html)) = std::task::ready!(self.poll_boundary(cx)) else {
+            return Poll::Ready(None);
+        };
+        let script = if self.sent_script { "" } else { SWAP_SCRIPT };
+        self.sent_script = true;
+        Poll::Ready(Some(format!(
+            "{script}<template id=\"rsx-resolved-{id}\">{html}</template><script>$rsx({id})</script>"
+        )))
+    }
+}
+
+impl RenderStream {
+    /// Returns the next chunk, or `None` once every boundary has been sent.
+    pub async fn next(&mut self) -> Option<String> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Returns the shell, unless it was already sent.
+    #[cfg(feature = "ssg")]
+    pub(crate) fn take_shell(&mut self) -> Option<String> {
+        self.shell.take()
+    }
+
+    /// Poll for the next boundary whose resources have completed, returning its id and content.
+    /// Returns `None` once every boundary has been rendered.
+    pub(crate) fn poll_boundary(&mut self, cx: &mut Context<'_>) -> Poll<Option<(usize, String)>> {
+        // Resources woken from other threads are only polled here.
+        run_until_stalled(cx.waker());
+
+        let boundaries = &mut self.state.boundaries;
+        let Some(index) = boundaries.iter().position(|b| !b.state.is_pending()) else {
+            if boundaries.is_empty() {
+                return Poll::Ready(None);
+            }
+            self.state.waker.set(Some(cx.waker().clone()));
+            return Poll::Pending;
+        };
+        let Boundary { id, children, .. } = boundaries.remove(index);
+
+        let mut state = Some(std::mem::take(&mut self.state));
+        let html = with_stream(&mut state, || self.root.run_in(|| children.to_string()));
+        self.state = state.unwrap();
+        Poll::Ready(Some((id, html)))
+    }
+}
+
+impl Drop for RenderStream {
+    fn drop(&mut self) {
+        self.root.dispose();
+    }
+}
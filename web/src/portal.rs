@@ -0,0 +1,85 @@
+//! Rendering a subtree into a different DOM container.
+
+use rsx::component::Children;
+use rsx_reactive::root::on_cleanup;
+
+use crate::{
+    document,
+    node::{delegation::with_delegation_root, dom_node::DomNode, ssr_render::is_ssr},
+    utils,
+    view::View,
+};
+
+/// Where a [`Portal`] renders its children.
+pub enum PortalTarget {
+    /// An existing element.
+    Element(web_sys::Element),
+    /// A CSS selector, resolved with `document.querySelector` when the portal is rendered.
+    Selector(String),
+}
+
+impl From<web_sys::Element> for PortalTarget {
+    fn from(element: web_sys::Element) -> Self {
+        Self::Element(element)
+    }
+}
+
+impl From<&str> for PortalTarget {
+    fn from(selector: &str) -> Self {
+        Self::Selector(selector.to_string())
+    }
+}
+
+impl From<String> for PortalTarget {
+    fn from(selector: String) -> Self {
+        Self::Selector(selector)
+    }
+}
+
+/// Render `children` at the end of `target` instead of at the portal's own position.
+///
+/// The children are created in the current reactive scope, so they have access to the same
+/// context and are removed from `target` when that scope is cleaned up. The portal itself renders
+/// nothing.
+///
+/// When rendering to a string, the children are not rendered at all since the target is not part
+/// of the rendered markup. They are mounted once the component is rendered on the client.
+///
+/// # Example
+/// ```no_run
+/// # use rsx::rsx;
+/// # use rsx_web::portal::Portal;
+/// fn app() -> rsx::Element {
+///     rsx!(
+///         <main>
+///             {Portal("#modals", || rsx!(<div class="modal">Hello</div>))}
+///         </main>
+///     )
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Portal(
+    target: impl Into<PortalTarget>,
+    children: impl Into<Children<rsx::Element>>,
+) -> rsx::Element {
+    if !is_ssr() {
+        let target = match target.into() {
+            PortalTarget::Element(element) => element,
+            PortalTarget::Selector(selector) => document()
+                .query_selector(&selector)
+                .unwrap()
+                .unwrap_or_else(|| panic!("portal target `{selector}` does not exist")),
+        };
+
+        // The target is outside of the render root, so delegated handlers would never be reached.
+        let view = with_delegation_root(None, || View::<DomNode>::from(children.into().call()));
+        let nodes = view.as_web_sys();
+        for node in &nodes {
+            target.append_child(node).unwrap();
+        }
+
+        on_cleanup(move || utils::remove_view_nodes(&nodes));
+    }
+
+    rsx::Element::Fragment(Vec::new())
+}
@@ -0,0 +1,230 @@
+//! Client-side routing.
+//!
+//! Routes are declared as an enum deriving [`Route`], with one `#[to("...")]` pattern per variant
+//! and a `#[not_found]` fallback:
+//!
+//! ```no_run
+//! # use rsx::rsx;
+//! # use rsx_web::router::{Route, Router};
+//! #[derive(Route)]
+//! enum AppRoute {
+//!     #[to("/")]
+//!     Home,
+//!     #[to("/posts/<id>")]
+//!     Post { id: u32 },
+//!     #[to("/files/<path..>")]
+//!     File(String),
+//!     #[not_found]
+//!     NotFound,
+//! }
+//!
+//! fn app() -> rsx::Element {
+//!     Router(|route| {
+//!         rsx::Element::Dynamic(Box::new(move || {
+//!             route.with(|route| match route {
+//!                 AppRoute::Home => rsx!(<h1>Home</h1>),
+//!                 AppRoute::Post { id } => rsx!(<h1>Post {id.to_string()}</h1>),
+//!                 AppRoute::File(path) => rsx!(<h1>{path.clone()}</h1>),
+//!                 AppRoute::NotFound => rsx!(<h1>Not found</h1>),
+//!             })
+//!         }))
+//!     })
+//! }
+//! ```
+//!
+//! Routes that share a layout are declared as a tree of [`NestedRoute`]s instead, rendered by a
+//! [`NestedRouter`]. See the [`NestedRoute`] documentation.
+//!
+//! In the browser, routers follow the History API: clicks on same-origin `<a>` elements
+//! and calls to [`navigate`] push a new history entry instead of loading a new page, and the back
+//! and forward buttons update the current route. On the server, the location is the URL passed
+//! to [`render_to_string_at`].
+
+mod nested;
+pub mod pattern;
+
+use std::cell::{Cell, RefCell};
+
+use rsx::events::EventOptions;
+use rsx_reactive::{
+    memos::create_memo,
+    root::on_cleanup,
+    signals::{ReadSignal, Signal, create_signal},
+};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+use crate::{
+    document,
+    node::{dom_node::add_event_listener, is_ssr, render_to_string},
+    window,
+};
+
+pub use nested::{NestedRoute, NestedRouter, Outlet, use_loader_data, use_params};
+pub use pattern::{Params, PatternError, RoutePattern};
+pub use rsx::Route;
+
+/// A set of routes that a path can be matched against.
+///
+/// This is usually derived, see the [module documentation](self). The derive checks patterns at
+/// compile time and parses each parameter into the type of its field with [`FromStr`], so
+/// `/posts/abc` does not match `/posts/<id>` if `id` is a `u32`.
+///
+/// [`FromStr`]: std::str::FromStr
+pub trait Route: Sized {
+    /// Returns the first route matching `path`, or the not found route if none does.
+    fn match_path(path: &str) -> Self;
+}
+
+thread_local! {
+    /// The location of the current [`Router`].
+    static LOCATION: Cell<Option<Signal<String>>> = const { Cell::new(None) };
+    /// The URL that routers start at while rendering with [`render_to_string_at`].
+    static SSR_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Match the current location against the routes `R` and render `view` with the matching route.
+///
+/// The route signal only changes when the location does, so `view` is only called once and
+/// should read the route inside a dynamic element. There should only be one router at a time,
+/// including [`NestedRouter`]s.
+#[allow(non_snake_case)]
+pub fn Router<R: Route + 'static>(
+    view: impl FnOnce(ReadSignal<R>) -> rsx::Element,
+) -> rsx::Element {
+    let location = create_location();
+    let route = create_memo(move || location.with(|path| R::match_path(path)));
+    view(route)
+}
+
+/// Returns the location of the current [`Router`] or [`NestedRouter`], i.e. its path, query
+/// string and fragment.
+///
+/// # Panics
+/// Panics if there is no router.
+pub fn use_location() -> ReadSignal<String> {
+    match LOCATION.with(Cell::get) {
+        Some(location) if location.is_alive() => *location,
+        _ => panic!("`use_location` must be called inside a `Router`"),
+    }
+}
+
+/// Navigate to `url` by pushing a new history entry.
+///
+/// Without a [`Router`], this loads `url` as a new page instead. While rendering on the server,
+/// this only updates the location of the router.
+pub fn navigate(url: &str) {
+    match LOCATION.with(Cell::get) {
+        Some(location) if location.is_alive() && is_ssr() => location.set(url.to_string()),
+        Some(location) if location.is_alive() => {
+            window()
+                .history()
+                .unwrap()
+                .push_state_with_url(&JsValue::NULL, "", Some(url))
+                .unwrap();
+            location.set(browser_location());
+        }
+        _ => window().location().set_href(url).unwrap(),
+    }
+}
+
+/// Render a component to an HTML string, with routers starting at `url`.
+pub fn render_to_string_at<C: Fn() -> rsx::Element>(url: &str, component: C) -> String {
+    with_location(url, || render_to_string(component))
+}
+
+/// Run `f` with routers rendered on the server starting at `url`.
+pub(crate) fn with_location<T>(url: &str, f: impl FnOnce() -> T) -> T {
+    /// Restores the previous location, even if `f` panics.
+    struct Reset(Option<String>);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            SSR_LOCATION.with(|location| location.replace(self.0.take()));
+        }
+    }
+
+    let _reset = Reset(SSR_LOCATION.with(|location| location.replace(Some(url.to_string()))));
+    f()
+}
+
+/// Create the location signal of a router, which is the current location until the current
+/// scope is cleaned up.
+fn create_location() -> Signal<String> {
+    let location = create_signal(initial_location());
+    let prev = LOCATION.with(|current| current.replace(Some(location)));
+    on_cleanup(move || LOCATION.with(|current| current.set(prev)));
+
+    if !is_ssr() {
+        listen(location);
+    }
+    location
+}
+
+fn initial_location() -> String {
+    if is_ssr() {
+        let location = SSR_LOCATION.with(|location| location.borrow().clone());
+        location.unwrap_or_else(|| "/".to_string())
+    } else {
+        browser_location()
+    }
+}
+
+/// The path, query string and fragment of the current page.
+fn browser_location() -> String {
+    let location = window().location();
+    let (pathname, search, hash) = (location.pathname(), location.search(), location.hash());
+    format!("{}{}{}", pathname.unwrap(), search.unwrap(), hash.unwrap())
+}
+
+/// Keep `location` in sync with the history and intercept link clicks, until the current scope is
+/// cleaned up.
+fn listen(location: Signal<String>) {
+    let popstate = rsx::EventListener {
+        handler: Box::new(move |_| location.set(browser_location())),
+        options: EventOptions::default(),
+    };
+    add_event_listener(&window(), "popstate".into(), popstate);
+
+    let click = rsx::EventListener {
+        handler: Box::new(|event| intercept_click(event.unchecked_into())),
+        options: EventOptions::default(),
+    };
+    add_event_listener(&document(), "click".into(), click);
+}
+
+/// Turn a click on a same-origin link into a call to [`navigate`]. Clicks that would open the
+/// link elsewhere, such as with a modifier key or a `target`, are left to the browser.
+fn intercept_click(event: web_sys::MouseEvent) {
+    if event.default_prevented()
+        || event.button() != 0
+        || event.meta_key()
+        || event.ctrl_key()
+        || event.shift_key()
+        || event.alt_key()
+    {
+        return;
+    }
+
+    let target = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+    let Some(anchor) = target.and_then(|target| target.closest("a[href]").ok().flatten()) else {
+        return;
+    };
+    let anchor = anchor.unchecked_into::<web_sys::HtmlAnchorElement>();
+    let external = anchor.origin() != window().location().origin().unwrap()
+        || !matches!(anchor.target().as_str(), "" | "_self")
+        || anchor.has_attribute("download")
+        || anchor.rel().split_whitespace().any(|rel| rel == "external");
+    if external {
+        return;
+    }
+
+    event.prevent_default();
+    navigate(&format!(
+        "{}{}{}",
+        anchor.pathname(),
+        anchor.search(),
+        anchor.hash()
+    ));
+}
@@ -0,0 +1,249 @@
+//! Nested routes with layouts and data loaders.
+
+use std::{cell::Cell, future::Future, rc::Rc};
+
+use rsx_reactive::{
+    context::{provide_context, use_context},
+    effects::create_effect,
+    futures::{Resource, create_resource},
+    memos::create_selector,
+    node::NodeHandle,
+    root::{create_child_scope, untrack, use_current_scope},
+    signals::{Signal, create_signal},
+};
+
+use super::{
+    create_location,
+    pattern::{Params, RoutePattern, path_segments},
+};
+
+/// A route in a tree of nested routes, rendered by a [`NestedRouter`].
+///
+/// A route with children is a layout: it matches the start of the path and renders the child
+/// matching the rest of it wherever it calls [`Outlet`]. Use `/` as the pattern of the child that
+/// is rendered when nothing is left. Routes are tried in order, so a catch-all route such as
+/// `/<path..>` should come last.
+///
+/// Each route can have a loader, which is started with the parameters of the route as soon as it
+/// matches. The loaders of all the matched routes run in parallel, and the view of each route and
+/// of its descendants can access its data with [`use_loader_data`].
+///
+/// Every matched route is rendered in its own scope. Navigating away from a route disposes its
+/// scope and those of its descendants only, so a layout keeps its state when navigating between
+/// its children.
+///
+/// # Example
+/// ```no_run
+/// # use rsx::rsx;
+/// # use rsx_web::router::{NestedRoute, NestedRouter, Outlet, use_loader_data, use_params};
+/// async fn fetch_post(id: String) -> String {
+///     // ...
+/// #   id
+/// }
+///
+/// fn app() -> rsx::Element {
+///     NestedRouter(vec![
+///         NestedRoute::new("/", || rsx!(<h1>Home</h1>)),
+///         NestedRoute::new("/posts", || rsx!(<main><nav>Posts</nav>{Outlet()}</main>))
+///             .child(NestedRoute::new("/", || rsx!(<p>Pick a post</p>)))
+///             .child(
+///                 NestedRoute::new("/<id>", || {
+///                     let post = use_loader_data::<String>().value();
+///                     rsx!(<article>{post.map(|post| post.clone().unwrap_or_default())}</article>)
+///                 })
+///                 .loader(|params| fetch_post(params.get("id").unwrap().to_string())),
+///             ),
+///     ])
+/// }
+/// ```
+pub struct NestedRoute {
+    pattern: RoutePattern,
+    view: Rc<dyn Fn() -> rsx::Element>,
+    loader: Option<Rc<dyn Fn(Params)>>,
+    children: Vec<NestedRoute>,
+}
+
+impl NestedRoute {
+    /// Create a route matching `pattern`, relative to its parent.
+    ///
+    /// # Panics
+    /// Panics if `pattern` is not a valid [`RoutePattern`].
+    pub fn new(pattern: &str, view: impl Fn() -> rsx::Element + 'static) -> Self {
+        Self {
+            pattern: RoutePattern::parse(pattern).unwrap_or_else(|error| panic!("{error}")),
+            view: Rc::new(view),
+            loader: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Load data for this route whenever it matches. The data can be accessed with
+    /// [`use_loader_data::<T>`](use_loader_data).
+    pub fn loader<T: 'static, F: Future<Output = T> + 'static>(
+        mut self,
+        loader: impl Fn(Params) -> F + 'static,
+    ) -> Self {
+        self.loader = Some(Rc::new(move |params| {
+            provide_context(create_resource(loader(params)));
+        }));
+        self
+    }
+
+    /// Add a child route, rendered in the [`Outlet`] of this route.
+    pub fn child(mut self, route: NestedRoute) -> Self {
+        self.children.push(route);
+        self
+    }
+}
+
+/// A matched route.
+#[derive(Clone)]
+struct Segment {
+    /// Unique among all the segments of the router, so that outlets know when to re-render.
+    id: u64,
+    /// The index of the route among its siblings.
+    index: usize,
+    /// The parameters captured by this route only.
+    params: Params,
+    view: Rc<dyn Fn() -> rsx::Element>,
+    scope: NodeHandle,
+}
+
+/// The parameters of the current route and of all its parents.
+#[derive(Clone)]
+struct RouteParams(Params);
+
+/// What an [`Outlet`] renders.
+#[derive(Clone, Copy)]
+struct OutletContext {
+    segments: Signal<Vec<Segment>>,
+    depth: usize,
+}
+
+/// Match the current location against `routes` and render the matching routes.
+#[allow(non_snake_case)]
+pub fn NestedRouter(routes: Vec<NestedRoute>) -> rsx::Element {
+    let location = create_location();
+    let scope = use_current_scope();
+    let segments = create_signal(Vec::<Segment>::new());
+    let next_id = Cell::new(0);
+
+    create_effect(move || {
+        let path = location.get_clone();
+        untrack(|| {
+            let path = path_segments(&path).collect::<Vec<_>>();
+            let mut matched = Vec::new();
+            match_routes(&routes, &path, &mut matched);
+
+            // Keep the segments that still match, and dispose the first one that does not
+            // along with its descendants.
+            let mut current = segments.get_clone();
+            let kept = current
+                .iter()
+                .zip(&matched)
+                .take_while(|(segment, (index, _, params))| {
+                    segment.index == *index && segment.params == *params
+                })
+                .count();
+            if let Some(segment) = current.get(kept) {
+                segment.scope.dispose();
+            }
+            current.truncate(kept);
+
+            let mut params = Params::default();
+            for segment in &current {
+                params.extend(segment.params.iter().map(|(k, v)| (k.into(), v.into())));
+            }
+            for (depth, (index, route, own_params)) in matched.into_iter().enumerate().skip(kept) {
+                params.extend(own_params.iter().map(|(k, v)| (k.into(), v.into())));
+                let parent = current.last().map_or(scope, |segment| segment.scope);
+                let segment_scope = parent.run_in(|| {
+                    create_child_scope(|| {
+                        provide_context(RouteParams(params.clone()));
+                        provide_context(OutletContext {
+                            segments,
+                            depth: depth + 1,
+                        });
+                        if let Some(loader) = &route.loader {
+                            loader(params.clone());
+                        }
+                    })
+                });
+                current.push(Segment {
+                    id: next_id.replace(next_id.get() + 1),
+                    index,
+                    params: own_params,
+                    view: route.view.clone(),
+                    scope: segment_scope,
+                });
+            }
+            segments.set(current);
+        });
+    });
+
+    render_level(segments, 0)
+}
+
+/// Push the routes matching `path`, from the outermost one, to `matched`. Returns `false` and
+/// leaves `matched` as it was if there is no match.
+fn match_routes<'a>(
+    routes: &'a [NestedRoute],
+    path: &[&str],
+    matched: &mut Vec<(usize, &'a NestedRoute, Params)>,
+) -> bool {
+    for (index, route) in routes.iter().enumerate() {
+        let Some((params, consumed)) = route.pattern.match_prefix(path) else {
+            continue;
+        };
+        let rest = &path[consumed..];
+        matched.push((index, route, params));
+        if route.children.is_empty() {
+            if rest.is_empty() {
+                return true;
+            }
+        } else if match_routes(&route.children, rest, matched) {
+            return true;
+        }
+        matched.pop();
+    }
+    false
+}
+
+/// Render the segment at `depth`, re-rendering it whenever it is replaced.
+fn render_level(segments: Signal<Vec<Segment>>, depth: usize) -> rsx::Element {
+    let id = create_selector(move || segments.with(|segments| segments.get(depth).map(|s| s.id)));
+    rsx::Element::Dynamic(Box::new(move || {
+        id.track();
+        let segment = segments.with_untracked(|segments| segments.get(depth).cloned());
+        match segment {
+            Some(segment) => segment.scope.run_in(|| untrack(|| (segment.view)())),
+            None => rsx::Element::Fragment(Vec::new()),
+        }
+    }))
+}
+
+/// Render the child route of the current route. Renders nothing if the current route is a leaf.
+///
+/// # Panics
+/// Panics if called outside of a route rendered by a [`NestedRouter`].
+#[allow(non_snake_case)]
+pub fn Outlet() -> rsx::Element {
+    let OutletContext { segments, depth } = use_context();
+    render_level(segments, depth)
+}
+
+/// Returns the parameters of the current route, including those of its parents.
+///
+/// # Panics
+/// Panics if called outside of a route rendered by a [`NestedRouter`].
+pub fn use_params() -> Params {
+    use_context::<RouteParams>().0
+}
+
+/// Returns the data loaded by the loader of the nearest route whose loader returns a `T`.
+///
+/// # Panics
+/// Panics if no such route is rendering the current one.
+pub fn use_loader_data<T: 'static>() -> Resource<T> {
+    use_context()
+}
@@ -0,0 +1,3 @@
+//! Route patterns and path matching, see [`rsx_route_pattern`].
+
+pub use rsx_route_pattern::{Params, PatternError, RoutePattern, path_segments};
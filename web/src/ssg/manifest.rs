@@ -0,0 +1,80 @@
+//! The assets linked from generated pages.
+
+use serde_json::Value;
+
+use super::SsgError;
+
+/// The scripts and stylesheets linked from every generated page, usually read from the manifest
+/// of a bundler with [`Manifest::from_vite`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// URLs of the entry scripts, loaded as modules at the end of the body.
+    pub scripts: Vec<String>,
+    /// URLs of the scripts imported by the entries, preloaded from the head.
+    pub preloads: Vec<String>,
+    /// URLs of the stylesheets, linked from the head.
+    pub styles: Vec<String>,
+}
+
+impl Manifest {
+    /// Read the assets of `entry` from a Vite manifest, i.e. the `.vite/manifest.json` file
+    /// written by `vite build` with `build.manifest` enabled. Every URL is absolute.
+    pub fn from_vite(json: &str, entry: &str) -> Result<Self, SsgError> {
+        let manifest = serde_json::from_str::<Value>(json)
+            .map_err(|error| SsgError::Manifest(format!("invalid manifest: {error}")))?;
+        let chunk = manifest
+            .get(entry)
+            .ok_or_else(|| SsgError::Manifest(format!("no entry `{entry}` in the manifest")))?;
+
+        let mut assets = Self::default();
+        assets.scripts.push(file(chunk, entry)?);
+        let mut visited = vec![entry];
+        let mut queue = vec![chunk];
+        while let Some(chunk) = queue.pop() {
+            for style in chunk
+                .get("css")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if let Some(style) = style.as_str() {
+                    push_unique(&mut assets.styles, format!("/{style}"));
+                }
+            }
+            for import in chunk
+                .get("imports")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let Some(key) = import.as_str() else {
+                    continue;
+                };
+                if visited.contains(&key) {
+                    continue;
+                }
+                visited.push(key);
+                let import = manifest.get(key).ok_or_else(|| {
+                    SsgError::Manifest(format!("`{key}` is imported but not in the manifest"))
+                })?;
+                push_unique(&mut assets.preloads, file(import, key)?);
+                queue.push(import);
+            }
+        }
+        Ok(assets)
+    }
+}
+
+/// Returns the URL of the output file of `chunk`.
+fn file(chunk: &Value, key: &str) -> Result<String, SsgError> {
+    match chunk.get("file").and_then(Value::as_str) {
+        Some(file) => Ok(format!("/{file}")),
+        None => Err(SsgError::Manifest(format!("`{key}` has no output file"))),
+    }
+}
+
+fn push_unique(urls: &mut Vec<String>, url: String) {
+    if !urls.contains(&url) {
+        urls.push(url);
+    }
+}
@@ -0,0 +1,328 @@
+//! Static site generation.
+//!
+//! A [`StaticSite`] prerenders an app at build time: every route is rendered on the server and
+//! written to `<out_dir>/<route>/index.html`, as a full document linking the assets of the
+//! client bundle, so the pages can be served by any static file server and hydrated in the
+//! browser.
+//!
+//! ```no_run
+//! # use rsx::rsx;
+//! # use rsx_web::{router::Params, ssg::{Manifest, StaticSite}};
+//! # fn app() -> rsx::Element { rsx!(<main></main>) }
+//! let manifest = std::fs::read_to_string("dist/.vite/manifest.json").unwrap();
+//! StaticSite::new(app)
+//!     .manifest(Manifest::from_vite(&manifest, "index.html").unwrap())
+//!     .route("/")
+//!     .route("/about")
+//!     .routes("/posts/<id>", || (1..=3).map(|id| Params::from_iter([("id", id.to_string())])))
+//!     .state(|path| format!("{{\"path\":\"{path}\"}}"))
+//!     .build("dist")
+//!     .unwrap();
+//! ```
+//!
+//! Tags registered with [`head`](crate::head) components are written into the `<head>` of each
+//! page. Pages wait for the [resources](rsx_reactive::futures::create_resource) of their
+//! [`Suspense`](crate::suspense::Suspense) boundaries, which may be woken from any thread, e.g.
+//! by an async runtime doing IO, and contain the resolved content of the boundaries instead of
+//! their fallback, so they do not need JavaScript to show it. Rendering is strict: a page fails to build if the app panics,
+//! if the resources do not complete within the [timeout](StaticSite::timeout), or if a resource
+//! created outside of a boundary is still loading once rendering is done, since the page would
+//! otherwise be missing content.
+
+pub mod manifest;
+
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt, fs,
+    future::poll_fn,
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use rsx::html::write_escaped;
+use rsx_reactive::{
+    context::provide_context,
+    futures::{block_on_timeout, create_suspense_scope},
+};
+
+use crate::{
+    document,
+    head::{HeadManager, RenderedPage},
+    node::{render_to_stream, stream_render},
+    router::{self, Params, PatternError, RoutePattern, pattern::path_segments},
+};
+
+pub use manifest::Manifest;
+
+/// The id of the script element holding the initial state of a page, see [`initial_state`].
+pub const STATE_ID: &str = "rsx-state";
+
+type Enumerate = Box<dyn Fn() -> Vec<Params>>;
+
+type State = Box<dyn Fn(&str) -> String>;
+
+/// An app to prerender, along with its routes. See the [module documentation](self).
+pub struct StaticSite<C> {
+    app: C,
+    routes: Vec<(RoutePattern, Enumerate)>,
+    manifest: Manifest,
+    state: Option<State>,
+    timeout: Duration,
+}
+
+/// An error that occurred while generating a site.
+#[derive(Debug)]
+pub enum SsgError {
+    /// A route could not be turned into a path.
+    Pattern(PatternError),
+    /// A path cannot be written to a file, e.g. because it contains `..`.
+    InvalidPath(String),
+    /// The app panicked while rendering `path`.
+    Render { path: String, message: String },
+    /// Resources were still loading once `path` was rendered, or after the timeout.
+    UnresolvedResources { path: String },
+    /// The asset manifest is invalid.
+    Manifest(String),
+    /// A file could not be written.
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for SsgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsgError::Pattern(error) => error.fmt(f),
+            SsgError::InvalidPath(path) => write!(f, "cannot write `{path}` to a file"),
+            SsgError::Render { path, message } => write!(f, "failed to render `{path}`: {message}"),
+            SsgError::UnresolvedResources { path } => write!(
+                f,
+                "failed to render `{path}`: resources were still loading once rendering was done"
+            ),
+            SsgError::Manifest(message) => f.write_str(message),
+            SsgError::Io { path, error } => {
+                write!(f, "failed to write {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SsgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SsgError::Pattern(error) => Some(error),
+            SsgError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<PatternError> for SsgError {
+    fn from(error: PatternError) -> Self {
+        SsgError::Pattern(error)
+    }
+}
+
+impl<C: Fn() -> rsx::Element> StaticSite<C> {
+    /// Create a site rendering `app`, usually the same component that is rendered in the
+    /// browser. The app is rendered once per page, with routers starting at its path.
+    pub fn new(app: C) -> Self {
+        Self {
+            app,
+            routes: Vec::new(),
+            manifest: Manifest::default(),
+            state: None,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Add a page for `path`.
+    ///
+    /// # Panics
+    /// Panics if `path` is not a valid [`RoutePattern`].
+    pub fn route(self, path: &str) -> Self {
+        self.routes(path, || [Params::default()])
+    }
+
+    /// Add a page for every set of parameters returned by `enumerate`, e.g. one per post for
+    /// `/posts/<id>`.
+    ///
+    /// # Panics
+    /// Panics if `pattern` is not a valid [`RoutePattern`].
+    pub fn routes<I: IntoIterator<Item = Params>>(
+        mut self,
+        pattern: &str,
+        enumerate: impl Fn() -> I + 'static,
+    ) -> Self {
+        let pattern = RoutePattern::parse(pattern).unwrap_or_else(|error| panic!("{error}"));
+        let enumerate = Box::new(move || enumerate().into_iter().collect());
+        self.routes.push((pattern, enumerate));
+        self
+    }
+
+    /// Set the assets linked from every page.
+    pub fn manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Embed the JSON returned by `state` for the path of each page, which the client can read
+    /// back with [`initial_state`] instead of fetching it again.
+    pub fn state(mut self, state: impl Fn(&str) -> String + 'static) -> Self {
+        self.state = Some(Box::new(state));
+        self
+    }
+
+    /// Set how long a page waits for its resources before failing to build. Defaults to 30
+    /// seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the paths of every page, in the order routes were added.
+    pub fn paths(&self) -> Result<Vec<String>, SsgError> {
+        let mut paths = Vec::new();
+        for (pattern, enumerate) in &self.routes {
+            for params in enumerate() {
+                paths.push(pattern.format(&params)?);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Render the page for `path` to a full HTML document.
+    pub fn render_page(&self, path: &str) -> Result<String, SsgError> {
+        let page = panic::catch_unwind(AssertUnwindSafe(|| self.render_body(path))).map_err(
+            |payload| SsgError::Render {
+                path: path.to_string(),
+                message: panic_message(payload),
+            },
+        )??;
+
+        let mut html = String::from("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+        html += &page.head;
+        for url in &self.manifest.preloads {
+            html += &tag("<link rel=\"modulepreload\" href=\"", url, "\">");
+        }
+        for url in &self.manifest.styles {
+            html += &tag("<link rel=\"stylesheet\" href=\"", url, "\">");
+        }
+        html += "</head><body><div id=\"app\">";
+        html += &page.body;
+        html += "</div>";
+        if let Some(state) = &self.state {
+            // `<` is escaped so that the state cannot close the script element.
+            let state = state(path).replace('<', "\\u003c");
+            html +=
+                &format!("<script id=\"{STATE_ID}\" type=\"application/json\">{state}</script>");
+        }
+        for url in &self.manifest.scripts {
+            html += &tag("<script type=\"module\" src=\"", url, "\"></script>");
+        }
+        html += "</body></html>";
+        Ok(html)
+    }
+
+    /// Render every page into `out_dir`, returning the paths of the files that were written.
+    /// Stops at the first page that fails to render.
+    pub fn build(&self, out_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, SsgError> {
+        let mut files = Vec::new();
+        for path in self.paths()? {
+            let html = self.render_page(&path)?;
+            let file = output_file(out_dir.as_ref(), &path)?;
+            let dir = file.parent().unwrap();
+            fs::create_dir_all(dir).map_err(|error| SsgError::Io {
+                path: dir.to_path_buf(),
+                error,
+            })?;
+            fs::write(&file, html).map_err(|error| SsgError::Io {
+                path: file.clone(),
+                error,
+            })?;
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    /// Render the app and its head tags at `path`, waiting for every
+    /// [`Suspense`](crate::suspense::Suspense) boundary so that its content is rendered in place
+    /// of its fallback.
+    fn render_body(&self, path: &str) -> Result<RenderedPage, SsgError> {
+        let unresolved = || SsgError::UnresolvedResources {
+            path: path.to_string(),
+        };
+        let head = HeadManager::new();
+        router::with_location(path, || {
+            let suspense = Cell::new(None);
+            let mut stream = render_to_stream(|| {
+                provide_context(head.clone());
+                let (element, state) = create_suspense_scope(&self.app);
+                suspense.set(Some(state));
+                element
+            });
+            let html = async {
+                let mut html = stream.take_shell().unwrap_or_default();
+                // Boundaries nested in one that is not rendered yet, placed once it is.
+                let mut nested = Vec::new();
+                while let Some(boundary) = poll_fn(|cx| stream.poll_boundary(cx)).await {
+                    nested.push(boundary);
+                    while let Some(index) = nested
+                        .iter()
+                        .position(|(id, content)| stream_render::splice(&mut html, *id, content))
+                    {
+                        nested.remove(index);
+                    }
+                }
+                html
+            };
+            let html = block_on_timeout(html, self.timeout).ok_or_else(unresolved)?;
+            match suspense.get() {
+                Some(state) if state.is_pending() => Err(unresolved()),
+                // The head is read before the stream is dropped, which removes every tag.
+                _ => Ok(RenderedPage {
+                    head: head.to_html(),
+                    body: html,
+                }),
+            }
+        })
+    }
+}
+
+/// Returns the JSON embedded in the current page with [`StaticSite::state`], if any.
+pub fn initial_state() -> Option<String> {
+    document()
+        .get_element_by_id(STATE_ID)
+        .and_then(|element| element.text_content())
+}
+
+/// Returns the file the page for `path` is written to.
+fn output_file(out_dir: &Path, path: &str) -> Result<PathBuf, SsgError> {
+    let mut file = out_dir.to_path_buf();
+    for segment in path_segments(path) {
+        if matches!(segment, "." | "..") || segment.contains('\\') {
+            return Err(SsgError::InvalidPath(path.to_string()));
+        }
+        file.push(segment);
+    }
+    file.push("index.html");
+    Ok(file)
+}
+
+/// Returns `start`, then `url` escaped, then `end`.
+fn tag(start: &str, url: &str, end: &str) -> String {
+    let mut tag = start.to_string();
+    write_escaped(&mut tag, url).unwrap();
+    tag + end
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "the app panicked".to_string(),
+        },
+    }
+}
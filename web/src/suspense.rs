@@ -0,0 +1,61 @@
+//! Showing a fallback while resources load.
+
+use std::cell::Cell;
+
+use rsx_reactive::{futures::create_suspense_scope, memos::create_selector};
+
+use crate::node::stream_render;
+
+/// Render `fallback` until the [resources](rsx_reactive::futures::create_resource) created by
+/// `children` have completed, and `children` after that.
+///
+/// The children are only revealed once: resources created inside the boundary after that, e.g.
+/// by an effect reloading some data, do not bring the fallback back. Their own loading state can
+/// be shown with [`Resource::is_loading`](rsx_reactive::futures::Resource::is_loading) instead.
+///
+/// When rendering to a string, the fallback is rendered if the resources have not completed by
+/// the time the markup is produced. When rendering to a stream, the fallback is sent first and
+/// replaced by the children in a later chunk, see [`render_to_stream`].
+///
+/// # Example
+/// ```no_run
+/// # use rsx::rsx;
+/// # use rsx_reactive::futures::create_resource;
+/// # use rsx_web::suspense::Suspense;
+/// # async fn fetch_user() -> String { String::new() }
+/// fn app() -> rsx::Element {
+///     Suspense(
+///         || rsx!(<p>Loading...</p>),
+///         || {
+///             let user = create_resource(fetch_user()).value();
+///             rsx!(<p>{user.map(|user| user.clone().unwrap_or_default())}</p>)
+///         },
+///     )
+/// }
+/// ```
+///
+/// [`render_to_stream`]: crate::node::render_to_stream
+#[allow(non_snake_case)]
+pub fn Suspense(
+    fallback: impl Fn() -> rsx::Element + 'static,
+    children: impl Fn() -> rsx::Element + 'static,
+) -> rsx::Element {
+    let (rendered, state) = create_suspense_scope(&children);
+    if stream_render::is_streaming() && state.is_pending() {
+        return stream_render::suspend(state, rendered, fallback());
+    }
+
+    let ready = create_selector(move || !state.is_pending());
+    let rendered = Cell::new(Some(rendered));
+    rsx::Element::Dynamic(Box::new(move || {
+        // `ready` is only tracked while the fallback is shown, so that the children are never
+        // replaced once they are revealed.
+        if !ready.get_untracked() {
+            ready.track();
+            return fallback();
+        }
+        // The children rendered with the resources are shown first, and rendered again if the
+        // element is, e.g. when it is displayed twice.
+        rendered.take().unwrap_or_else(&children)
+    }))
+}
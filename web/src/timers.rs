@@ -0,0 +1,59 @@
+//! The browser implementation of the [timer clock](rsx_reactive::timers).
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
+
+use rsx_reactive::timers::{Clock, TimeoutId, has_clock, set_clock};
+use web_sys::wasm_bindgen::{JsCast, prelude::Closure};
+
+use crate::window;
+
+thread_local! {
+    /// The callbacks of the pending timeouts, dropped once they fire or are cleared.
+    static CALLBACKS: RefCell<HashMap<TimeoutId, Closure<dyn FnMut()>>> = RefCell::default();
+}
+
+/// A [`Clock`] using `setTimeout`. It is set when rendering to the DOM unless another clock was
+/// set.
+pub struct WindowClock;
+
+impl Clock for WindowClock {
+    fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce()>) -> TimeoutId {
+        let id = Rc::new(Cell::new(0));
+        let closure = Closure::<dyn FnMut()>::once({
+            let id = id.clone();
+            move || {
+                // Dropping the closure while it runs is deferred until it returns.
+                let closure = CALLBACKS.with(|callbacks| callbacks.borrow_mut().remove(&id.get()));
+                callback();
+                drop(closure);
+            }
+        });
+        let delay = i32::try_from(delay.as_millis()).unwrap_or(i32::MAX);
+        let handle = window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                delay,
+            )
+            .unwrap();
+        id.set(handle);
+        CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(handle, closure));
+        handle
+    }
+
+    fn clear_timeout(&self, id: TimeoutId) {
+        window().clear_timeout_with_handle(id);
+        CALLBACKS.with(|callbacks| callbacks.borrow_mut().remove(&id));
+    }
+}
+
+/// Use a [`WindowClock`] unless a clock was already set.
+pub(crate) fn install_clock() {
+    if !has_clock() {
+        set_clock(WindowClock);
+    }
+}
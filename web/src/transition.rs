@@ -0,0 +1,374 @@
+//! Enter and leave animations.
+//!
+//! [`Transition`] and [`TransitionGroup`] add classes to the elements they render while they
+//! enter and leave the document, in the same way as Vue: with a name of `fade`, an entering
+//! element gets `fade-enter-from` and `fade-enter-active` when it is inserted, `fade-enter-from`
+//! is swapped for `fade-enter-to` on the next frame, and both other classes are removed once the
+//! duration has elapsed. Leaving elements get the `leave` classes in the same way and are only
+//! removed from the document once the duration has elapsed.
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use rsx::rsx;
+//! # use rsx_reactive::signals::create_signal;
+//! # use rsx_web::transition::Transition;
+//! fn app() -> rsx::Element {
+//!     let open = create_signal(false);
+//!     rsx!(
+//!         <button on:click={move |_| open.set_fn(|open| !open)}>Toggle</button>
+//!         {Transition("fade", Duration::from_millis(200), move || open.get(), || {
+//!             rsx!(<p>Hello</p>)
+//!         })}
+//!     )
+//! }
+//! ```
+//!
+//! with a stylesheet such as:
+//!
+//! ```css
+//! .fade-enter-active, .fade-leave-active { transition: opacity 200ms; }
+//! .fade-enter-from, .fade-leave-to { opacity: 0; }
+//! ```
+//!
+//! The frames are run by the [frame scheduler](rsx_reactive::motion) of the thread, which is set
+//! to an [`AnimationFrameScheduler`] when rendering to the DOM unless another one was set. When
+//! rendering to a string, the elements are rendered without any class.
+
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc, time::Duration};
+
+use rsx::node_ref::{NodeRef, create_node_ref};
+use rsx_reactive::{
+    effects::create_effect,
+    memos::create_selector,
+    motion::{FrameScheduler, has_frame_scheduler, request_frame, set_frame_scheduler},
+    node::NodeHandle,
+    root::{create_child_scope, untrack, use_current_scope},
+    signals::create_signal,
+};
+use web_sys::{
+    js_sys::{Function, Object, Reflect},
+    wasm_bindgen::{JsCast, JsValue, prelude::Closure},
+};
+
+use crate::{
+    node::{DomNode, is_ssr},
+    view::View,
+    window,
+};
+
+/// A [`FrameScheduler`] running frames with `requestAnimationFrame`.
+pub struct AnimationFrameScheduler;
+
+impl FrameScheduler for AnimationFrameScheduler {
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>) {
+        let callback = Closure::once_into_js(callback);
+        window()
+            .request_animation_frame(callback.unchecked_ref())
+            .unwrap();
+    }
+}
+
+/// Use an [`AnimationFrameScheduler`] unless a frame scheduler was already set.
+pub(crate) fn install_frame_scheduler() {
+    if !has_frame_scheduler() {
+        set_frame_scheduler(AnimationFrameScheduler);
+    }
+}
+
+/// The property of a DOM node holding the function that starts its leave animation.
+const LEAVE_HOOK: &str = "$rsxLeave";
+
+/// The property set on a DOM node while its leave animation runs.
+const LEAVING: &str = "$rsxLeaving";
+
+/// Start the leave animation of `node` if it has one. Returns `false` if it has none and should
+/// be removed at once. Nodes that are already leaving are left alone and return `true`.
+pub(crate) fn start_leave(node: &web_sys::Node) -> bool {
+    if Reflect::get(node, &JsValue::from_str(LEAVING)).is_ok_and(|leaving| leaving.is_truthy()) {
+        return true;
+    }
+    let hook = Reflect::get(node, &JsValue::from_str(LEAVE_HOOK)).ok();
+    let Some(hook) = hook.and_then(|hook| hook.dyn_into::<Function>().ok()) else {
+        return false;
+    };
+    Reflect::set(node, &JsValue::from_str(LEAVING), &JsValue::TRUE).unwrap();
+    Reflect::delete_property(
+        node.unchecked_ref::<Object>(),
+        &JsValue::from_str(LEAVE_HOOK),
+    )
+    .unwrap();
+    hook.call0(&JsValue::NULL).unwrap();
+    true
+}
+
+/// The classes and duration of a transition.
+#[derive(Clone, Copy)]
+struct Classes {
+    name: &'static str,
+    duration: Duration,
+}
+
+impl Classes {
+    /// Run the `phase` (`enter` or `leave`) of the transition on `element`, then call `done`.
+    fn run(self, element: web_sys::Element, phase: &'static str, done: impl FnOnce() + 'static) {
+        let class = move |suffix: &str| format!("{}-{phase}-{suffix}", self.name);
+        let classes = element.class_list();
+        classes.add_2(&class("from"), &class("active")).unwrap();
+        after_frames(Duration::ZERO, move || {
+            classes.remove_1(&class("from")).unwrap();
+            classes.add_1(&class("to")).unwrap();
+            after_frames(self.duration, move || {
+                classes.remove_2(&class("active"), &class("to")).unwrap();
+                done();
+            });
+        });
+    }
+
+    /// Animate the elements at the top of `element`, and those rendered by its dynamic children
+    /// if `dynamic` is set.
+    fn apply(self, element: rsx::Element, dynamic: bool) -> rsx::Element {
+        match element {
+            rsx::Element::HTMLElement(mut element) => {
+                let node_ref = *element.node_ref.get_or_insert_with(create_node_ref);
+                self.observe(node_ref);
+                rsx::Element::HTMLElement(element)
+            }
+            rsx::Element::Fragment(children) => rsx::Element::Fragment(
+                children
+                    .into_iter()
+                    .map(|child| self.apply(child, dynamic))
+                    .collect(),
+            ),
+            rsx::Element::Dynamic(f) if dynamic => {
+                rsx::Element::Dynamic(Box::new(move || self.apply(f(), dynamic)))
+            }
+            element => element,
+        }
+    }
+
+    /// Run the enter animation once the node of `node_ref` is created, and set up its leave
+    /// animation.
+    fn observe(self, node_ref: NodeRef) {
+        create_effect(move || {
+            let Some(element) = node_ref.get::<web_sys::Element>() else {
+                return;
+            };
+            untrack(|| {
+                self.run(element.clone(), "enter", || {});
+                // The scope of the element is disposed by the time it leaves, so the hook is
+                // stored on the node itself.
+                let node = element.clone();
+                let hook = Closure::once_into_js(move || {
+                    self.run(node.clone(), "leave", move || node.remove());
+                });
+                Reflect::set(&element, &JsValue::from_str(LEAVE_HOOK), &hook).unwrap();
+            });
+        });
+    }
+}
+
+/// Call `f` once `duration` has elapsed since the next frame, or at once without a frame
+/// scheduler.
+fn after_frames(duration: Duration, f: impl FnOnce() + 'static) {
+    fn wait(start: Option<f64>, duration: f64, f: Box<dyn FnOnce()>) {
+        request_frame(move |now| {
+            let start = start.unwrap_or(now);
+            match now - start >= duration {
+                true => f(),
+                false => wait(Some(start), duration, f),
+            }
+        });
+    }
+
+    match has_frame_scheduler() {
+        true => wait(None, duration.as_secs_f64() * 1000.0, Box::new(f)),
+        false => f(),
+    }
+}
+
+/// Render `children` while `show` returns `true`, animating its elements as they enter and
+/// leave. See the [module documentation](self) for the classes that are added.
+#[allow(non_snake_case)]
+pub fn Transition(
+    name: &'static str,
+    duration: Duration,
+    show: impl Fn() -> bool + 'static,
+    children: impl Fn() -> rsx::Element + 'static,
+) -> rsx::Element {
+    let classes = Classes { name, duration };
+    let show = create_selector(show);
+    rsx::Element::Dynamic(Box::new(move || match show.get() {
+        true if is_ssr() => children(),
+        true => classes.apply(children(), false),
+        false => rsx::Element::Fragment(Vec::new()),
+    }))
+}
+
+/// Render the items returned by `each` in a `tag` element, animating their elements as they
+/// enter and leave. Items are matched by `key` whenever `each` changes, so only the items that
+/// were added or removed are animated, and the others are moved in place. See the
+/// [module documentation](self) for the classes that are added.
+///
+/// Only the elements at the top of an item are animated and moved, so `view` should render
+/// elements, such as a `<li>`, rather than text. Backends that do not fill in node references,
+/// such as the one of `rsx-testing`, render all the items again whenever `each` changes.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use rsx::rsx;
+/// # use rsx_reactive::signals::create_signal;
+/// # use rsx_web::transition::TransitionGroup;
+/// # fn app() -> rsx::Element {
+/// let todos = create_signal(vec![(1, "Write docs"), (2, "Ship it")]);
+/// TransitionGroup(
+///     "list",
+///     Duration::from_millis(200),
+///     "ul",
+///     move || todos.get_clone(),
+///     |(id, _)| *id,
+///     |(_, todo)| rsx!(<li>{todo}</li>),
+/// )
+/// # }
+/// ```
+#[allow(non_snake_case)]
+pub fn TransitionGroup<T: 'static, K: Eq + Hash + 'static>(
+    name: &'static str,
+    duration: Duration,
+    tag: &'static str,
+    each: impl Fn() -> Vec<T> + 'static,
+    key: impl Fn(&T) -> K + 'static,
+    view: impl Fn(T) -> rsx::Element + 'static,
+) -> rsx::Element {
+    let container = |node_ref, children| {
+        rsx::Element::HTMLElement(rsx::HTMLElement {
+            name: tag.to_string(),
+            attributes: HashMap::new(),
+            event_listeners: HashMap::new(),
+            classes: HashMap::new(),
+            styles: HashMap::new(),
+            properties: HashMap::new(),
+            inner_html: None,
+            node_ref,
+            children,
+        })
+    };
+    if is_ssr() {
+        return container(None, each().into_iter().map(view).collect());
+    }
+
+    let classes = Classes { name, duration };
+    let (each, key, view) = (Rc::new(each), Rc::new(key), Rc::new(view));
+    let items = Rc::new(RefCell::new(Vec::<(K, GroupItem)>::new()));
+    // Render an item in its own scope, which is disposed once the item is removed.
+    let render_item = {
+        let (key, view) = (key.clone(), view.clone());
+        move |item: T| {
+            let key = key(&item);
+            let mut rendered = None;
+            let scope = create_child_scope(|| {
+                let element = classes.apply(view(item), false);
+                let mut refs = Vec::new();
+                collect_node_refs(&element, &mut refs);
+                rendered = Some((element, refs));
+            });
+            let (element, refs) = rendered.unwrap();
+            (element, (key, GroupItem { refs, scope }))
+        }
+    };
+
+    // All the items are rendered by a dynamic child. It only renders again when the list changes
+    // on backends without node references, and the items are moved by hand otherwise.
+    let rerender = create_signal(());
+    let list = {
+        let (each, items, render_item) = (each.clone(), items.clone(), render_item.clone());
+        rsx::Element::Dynamic(Box::new(move || {
+            rerender.track();
+            untrack(|| {
+                let (children, rendered) = each().into_iter().map(&render_item).unzip();
+                *items.borrow_mut() = rendered;
+                rsx::Element::Fragment(children)
+            })
+        }))
+    };
+
+    let node_ref: NodeRef = create_node_ref();
+    let scope = use_current_scope();
+    let mut first = true;
+    create_effect(move || {
+        let list = each();
+        if std::mem::take(&mut first) {
+            return;
+        }
+        untrack(|| {
+            let Some(container) = node_ref.get_raw() else {
+                rerender.set(());
+                return;
+            };
+            let mut old: HashMap<K, GroupItem> = items.take().into_iter().collect();
+            let new: Vec<_> = list
+                .into_iter()
+                .map(|item| match old.remove_entry(&key(&item)) {
+                    Some(existing) => existing,
+                    None => scope.run_in(|| {
+                        let (element, item) = render_item(item);
+                        // Rendering the element creates its nodes and fills in the references.
+                        drop(View::<DomNode>::from(element));
+                        item
+                    }),
+                })
+                .collect();
+            for item in old.into_values() {
+                // The references are cleared when the scope of the item is disposed.
+                let nodes = item.nodes();
+                item.scope.dispose();
+                for node in nodes {
+                    if !start_leave(&node) {
+                        let _ = container.remove_child(&node);
+                    }
+                }
+            }
+            let mut next: Option<web_sys::Node> = None;
+            for node in new
+                .iter()
+                .rev()
+                .flat_map(|(_, item)| item.nodes().into_iter().rev())
+            {
+                if node.parent_node().as_ref() != Some(&container) || node.next_sibling() != next {
+                    container.insert_before(&node, next.as_ref()).unwrap();
+                }
+                next = Some(node);
+            }
+            *items.borrow_mut() = new;
+        });
+    });
+    container(Some(node_ref), vec![list])
+}
+
+/// An item rendered by a [`TransitionGroup`].
+struct GroupItem {
+    refs: Vec<NodeRef>,
+    scope: NodeHandle,
+}
+
+impl GroupItem {
+    /// The nodes at the top of the item.
+    fn nodes(&self) -> Vec<web_sys::Node> {
+        self.refs
+            .iter()
+            .filter_map(|node_ref| node_ref.get_raw())
+            .collect()
+    }
+}
+
+/// Collect the references of the elements at the top of `element`.
+fn collect_node_refs(element: &rsx::Element, refs: &mut Vec<NodeRef>) {
+    match element {
+        rsx::Element::HTMLElement(element) => refs.extend(element.node_ref),
+        rsx::Element::Fragment(children) => {
+            for child in children {
+                collect_node_refs(child, refs);
+            }
+        }
+        _ => {}
+    }
+}
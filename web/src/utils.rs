@@ -27,3 +27,23 @@ pub fn get_nodes_between(start: &web_sys::Node, end: &web_sys::Node) -> Vec<web_
 
     nodes
 }
+
+/// Remove the nodes of a rendered view from their parent.
+///
+/// Dynamic views replace the nodes between their start and end markers, so everything from the
+/// first to the last node of the view is removed rather than only `nodes`.
+pub fn remove_view_nodes(nodes: &[web_sys::Node]) {
+    let (Some(first), Some(last)) = (nodes.first(), nodes.last()) else {
+        return;
+    };
+    let Some(parent) = first.parent_node() else {
+        return;
+    };
+    if first != last {
+        for node in get_nodes_between(first, last) {
+            parent.remove_child(&node).unwrap();
+        }
+        parent.remove_child(last).unwrap();
+    }
+    parent.remove_child(first).unwrap();
+}
@@ -1,7 +1,7 @@
 use rsx::component::Children;
 use smallvec::{SmallVec, smallvec};
 
-use crate::node::ViewHtmlNode;
+use crate::node::WebSysNode;
 
 /// Represents a view tree.
 ///
@@ -46,7 +46,7 @@ impl<T> View<T> {
     /// Create a flat list of all the web-sys nodes in the view.
     pub fn as_web_sys(&self) -> Vec<web_sys::Node>
     where
-        T: ViewHtmlNode,
+        T: WebSysNode,
     {
         self.nodes
             .iter()
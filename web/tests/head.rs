@@ -0,0 +1,92 @@
+use rsx::rsx;
+use rsx_reactive::{
+    context::provide_context,
+    root::{create_child_scope, create_root},
+    signals::create_signal,
+};
+use rsx_web::head::{
+    HeadManager, Link, Meta, RenderedPage, Style, Title, render_to_string_with_head,
+};
+
+#[test]
+fn render_head_with_body() {
+    let page = render_to_string_with_head(|| {
+        rsx!(
+            <main>
+                {Title("Blog")}
+                {Meta(&[("name", "description"), ("content", "All the posts")])}
+                {Link(&[("rel", "canonical"), ("href", "https://example.com/")])}
+                <article>
+                    {Title("First post")}
+                    {Meta(&[("name", "description"), ("content", "Tom & Jerry")])}
+                    {Meta(&[("property", "og:title"), ("content", "First post")])}
+                    {Style("h1 > a { color: red }")}
+                    <h1>First post</h1>
+                </article>
+            </main>
+        )
+    });
+    assert_eq!(
+        page,
+        RenderedPage {
+            head: concat!(
+                r#"<link rel="canonical" href="https://example.com/">"#,
+                "<title>First post</title>",
+                r#"<meta name="description" content="Tom &amp; Jerry">"#,
+                r#"<meta property="og:title" content="First post">"#,
+                "<style>h1 > a { color: red }</style>",
+            )
+            .to_string(),
+            body: "<main><article><h1>First post</h1></article></main>".to_string(),
+        }
+    );
+}
+
+#[test]
+fn last_mounted_tag_wins_until_cleanup() {
+    let head = HeadManager::new();
+    let _ = create_root(|| {
+        provide_context(head.clone());
+        let title = create_signal("Home".to_string());
+        let _ = Title(title);
+        let _ = Meta(&[("charset", "utf-8")]);
+        assert_eq!(
+            head.to_html(),
+            r#"<title>Home</title><meta charset="utf-8">"#
+        );
+
+        let page = create_child_scope(|| {
+            let _ = Title("Settings");
+            let _ = Link(&[("rel", "icon"), ("href", "/settings.svg")]);
+        });
+        title.set("Home (1)".to_string());
+        assert_eq!(
+            head.to_html(),
+            r#"<meta charset="utf-8"><title>Settings</title><link rel="icon" href="/settings.svg">"#
+        );
+
+        page.dispose();
+        assert_eq!(
+            head.to_html(),
+            r#"<title>Home (1)</title><meta charset="utf-8">"#
+        );
+    });
+}
+
+#[test]
+fn collect_scoped_styles_once() {
+    const STYLE: rsx::css::ScopedStyle = rsx::css!("p { margin: 0 }");
+    let page = render_to_string_with_head(|| {
+        let item = || rsx!(<p css={STYLE}>Item</p>);
+        rsx!(<div>{item()}{item()}</div>)
+    });
+    let class = STYLE.class();
+    assert_eq!(
+        page.head,
+        format!(r#"<style data-rsx-scope="{class}">p.{class}{{margin:0}}</style>"#)
+    );
+    assert_eq!(
+        page.body,
+        format!(r#"<div><p class="{class}">Item</p><p class="{class}">Item</p></div>"#)
+    );
+}
@@ -0,0 +1,27 @@
+use rsx_web::node::namespace;
+
+#[test]
+fn element_namespaces() {
+    assert_eq!(namespace::element("div", None), None);
+    assert_eq!(namespace::element("svg", None), Some(namespace::SVG));
+    assert_eq!(
+        namespace::element("circle", Some(namespace::SVG)),
+        Some(namespace::SVG)
+    );
+    assert_eq!(namespace::element("math", None), Some(namespace::MATHML));
+
+    // `<foreignObject>` is an SVG element whose children are HTML.
+    let foreign = namespace::element("foreignObject", Some(namespace::SVG));
+    assert_eq!(foreign, Some(namespace::SVG));
+    assert_eq!(namespace::children("foreignObject", foreign), None);
+    assert_eq!(namespace::children("g", foreign), Some(namespace::SVG));
+}
+
+#[test]
+fn attribute_namespaces() {
+    assert_eq!(namespace::attribute("href"), None);
+    assert_eq!(namespace::attribute("xlink:href"), Some(namespace::XLINK));
+    assert_eq!(namespace::attribute("xml:lang"), Some(namespace::XML));
+    assert_eq!(namespace::attribute("xmlns"), Some(namespace::XMLNS));
+    assert_eq!(namespace::attribute("xmlns:xlink"), Some(namespace::XMLNS));
+}
@@ -0,0 +1,162 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rsx::rsx;
+use rsx_reactive::root::on_cleanup;
+use rsx_web::router::{
+    NestedRoute, NestedRouter, Outlet, Params, Route, RoutePattern, Router, navigate,
+    render_to_string_at, use_loader_data, use_location, use_params,
+};
+
+#[derive(Debug, PartialEq, Route)]
+enum AppRoute {
+    #[to("/")]
+    Home,
+    #[to("/posts/<id>")]
+    Post { id: u32 },
+    #[to("/posts/<id>/comments/<comment>")]
+    Comment(u32, String),
+    #[to("/files/<path..>")]
+    File { path: String },
+    #[not_found]
+    NotFound,
+}
+
+#[test]
+fn match_patterns() {
+    let pattern = RoutePattern::parse("/posts/<id>").unwrap();
+    let params = pattern.matches("/posts/42?sort=new#top").unwrap();
+    assert_eq!(params.get("id"), Some("42"));
+    assert!(pattern.matches("/posts/").is_none());
+    assert!(pattern.matches("/posts/42/edit").is_none());
+
+    let wildcard = RoutePattern::parse("/files/<path..>").unwrap();
+    let params = wildcard.matches("/files/a/b//c.txt").unwrap();
+    assert_eq!(params.get("path"), Some("a/b/c.txt"));
+    assert_eq!(wildcard.matches("/files").unwrap().get("path"), Some(""));
+
+    assert!(RoutePattern::parse("/<rest..>/end").is_err());
+    assert!(RoutePattern::parse("/posts-<id>").is_err());
+    assert!(RoutePattern::parse("/<id>/<id>").is_err());
+    assert!(RoutePattern::parse("/users/<é>").is_err());
+}
+
+#[test]
+fn decode_params() {
+    let pattern = RoutePattern::parse("/users/<name>").unwrap();
+    let params = pattern.matches("/users/a%20b%C3%A9").unwrap();
+    assert_eq!(params.get("name"), Some("a bé"));
+    let params = pattern.matches("/users/100%25%zz").unwrap();
+    assert_eq!(params.get("name"), Some("100%%zz"));
+
+    let wildcard = RoutePattern::parse("/files/<path..>").unwrap();
+    let params = wildcard.matches("/files/my%20docs/a%2Fb.txt").unwrap();
+    assert_eq!(params.get("path"), Some("my docs/a/b.txt"));
+}
+
+#[test]
+fn derive_route() {
+    assert_eq!(AppRoute::match_path("/"), AppRoute::Home);
+    assert_eq!(AppRoute::match_path("/posts/7/"), AppRoute::Post { id: 7 });
+    assert_eq!(
+        AppRoute::match_path("/posts/7/comments/first"),
+        AppRoute::Comment(7, "first".to_string())
+    );
+    assert_eq!(
+        AppRoute::match_path("/files/docs/readme.md"),
+        AppRoute::File {
+            path: "docs/readme.md".to_string()
+        }
+    );
+    // Parameters that do not parse into their field do not match.
+    assert_eq!(AppRoute::match_path("/posts/abc"), AppRoute::NotFound);
+    assert_eq!(AppRoute::match_path("/about"), AppRoute::NotFound);
+}
+
+#[test]
+fn render_route_to_string() {
+    let app = || {
+        Router(|route| {
+            rsx::Element::Dynamic(Box::new(move || {
+                route.with(|route| match route {
+                    AppRoute::Post { id } => rsx!(<h1>Post {id.to_string()}</h1>),
+                    _ => rsx!(<h1>{use_location().get_clone()}</h1>),
+                })
+            }))
+        })
+    };
+    assert_eq!(render_to_string_at("/posts/3", app), "<h1>Post 3</h1>");
+    assert_eq!(render_to_string_at("/nope?q=1", app), "<h1>/nope?q=1</h1>");
+}
+
+#[test]
+fn nested_routes_render_in_outlets() {
+    let app = || {
+        NestedRouter(vec![
+            NestedRoute::new("/", || rsx!(<h1>Home</h1>)),
+            NestedRoute::new("/users/<user>", || {
+                let name = use_loader_data::<String>().value();
+                rsx!(<main><h1>{name.get_clone().unwrap()}</h1>{Outlet()}</main>)
+            })
+            .loader(|params| std::future::ready(params.get("user").unwrap().to_uppercase()))
+            .child(NestedRoute::new("/", || rsx!(<p>Profile</p>)))
+            .child(NestedRoute::new("/posts/<id>", || {
+                let params = use_params();
+                let user = params.get("user").unwrap();
+                rsx!(<p>{format!("Post {} by {user}", params.get("id").unwrap())}</p>)
+            })),
+        ])
+    };
+    assert_eq!(render_to_string_at("/", app), "<h1>Home</h1>");
+    assert_eq!(
+        render_to_string_at("/users/ada", app),
+        "<main><h1>ADA</h1><p>Profile</p></main>"
+    );
+    assert_eq!(
+        render_to_string_at("/users/ada/posts/1", app),
+        "<main><h1>ADA</h1><p>Post 1 by ada</p></main>"
+    );
+    assert_eq!(render_to_string_at("/nope", app), "");
+}
+
+#[test]
+fn navigation_only_replaces_changed_segments() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let loader = |log: &Rc<RefCell<Vec<String>>>, param: &'static str| {
+        let log = log.clone();
+        move |params: Params| {
+            let value = params.get(param).unwrap().to_string();
+            log.borrow_mut().push(format!("load {value}"));
+            let log = log.clone();
+            on_cleanup(move || log.borrow_mut().push(format!("dispose {value}")));
+            std::future::ready(())
+        }
+    };
+    let html = render_to_string_at("/docs/intro", || {
+        let view = NestedRouter(vec![
+            NestedRoute::new("/<section>", Outlet)
+                .loader(loader(&log, "section"))
+                .child(
+                    NestedRoute::new("/<page>", || rsx!(<p>Page</p>)).loader(loader(&log, "page")),
+                ),
+        ]);
+        navigate("/docs/setup");
+        navigate("/blog/news");
+        view
+    });
+    assert_eq!(html, "<p>Page</p>");
+    assert_eq!(
+        *log.borrow(),
+        [
+            "load docs",
+            "load intro",
+            "dispose intro",
+            "load setup",
+            "dispose docs",
+            "dispose setup",
+            "load blog",
+            "load news",
+            "dispose blog",
+            "dispose news",
+        ]
+    );
+}
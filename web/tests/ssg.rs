@@ -0,0 +1,234 @@
+#![cfg(feature = "ssg")]
+
+use std::{
+    fs,
+    future::{pending, poll_fn},
+    sync::mpsc,
+    task::Poll,
+    thread,
+    time::Duration,
+};
+
+use rsx::rsx;
+use rsx_reactive::futures::create_resource;
+use rsx_web::{
+    head::Title,
+    router::{Params, Route, Router},
+    ssg::{Manifest, SsgError, StaticSite},
+    suspense::Suspense,
+};
+
+#[derive(Clone, Route)]
+enum SiteRoute {
+    #[to("/")]
+    Home,
+    #[to("/posts/<id>")]
+    Post { id: u32 },
+    #[not_found]
+    NotFound,
+}
+
+fn site() -> rsx::Element {
+    Router(|route| {
+        rsx::Element::Dynamic(Box::new(move || match route.get_clone() {
+            SiteRoute::Home => rsx!(<h1>Home</h1>),
+            SiteRoute::Post { id } => Suspense(
+                || rsx!(<p>Loading</p>),
+                move || {
+                    let title = create_resource(async move { format!("Post {id}") }).value();
+                    let title = title.map(|title| title.clone().unwrap_or_default());
+                    rsx!(<h1>{Title(title)}{title}</h1>)
+                },
+            ),
+            SiteRoute::NotFound => rsx!(<h1>Not found</h1>),
+        }))
+    })
+}
+
+#[test]
+fn build_static_site() {
+    let out_dir = std::env::temp_dir().join(format!("rsx-ssg-{}", std::process::id()));
+    let manifest = Manifest {
+        scripts: vec!["/assets/index.js".to_string()],
+        preloads: Vec::new(),
+        styles: vec!["/assets/index.css".to_string()],
+    };
+    let files = StaticSite::new(site)
+        .manifest(manifest)
+        .route("/")
+        .routes("/posts/<id>", || {
+            (1..=2).map(|id| Params::from_iter([("id", id.to_string())]))
+        })
+        .state(|path| format!("{{\"path\":\"{path}</script>\"}}"))
+        .build(&out_dir)
+        .unwrap();
+
+    assert_eq!(
+        files,
+        [
+            out_dir.join("index.html"),
+            out_dir.join("posts/1/index.html"),
+            out_dir.join("posts/2/index.html"),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&files[1]).unwrap(),
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <title>Post 1</title><link rel=\"stylesheet\" href=\"/assets/index.css\"></head>\
+         <body><div id=\"app\"><h1>Post 1</h1></div>\
+         <script id=\"rsx-state\" type=\"application/json\">{\"path\":\"/posts/1\\u003c/script>\"}</script>\
+         <script type=\"module\" src=\"/assets/index.js\"></script></body></html>"
+    );
+    fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn fail_on_errors() {
+    let site = StaticSite::new(|| -> rsx::Element { panic!("boom") }).route("/");
+    let Err(SsgError::Render { path, message }) = site.render_page("/posts/1") else {
+        panic!("expected a render error");
+    };
+    assert_eq!((path.as_str(), message.as_str()), ("/posts/1", "boom"));
+    // The panic does not leave the page being rendered behind.
+    assert!(!rsx_web::node::is_ssr());
+    let html = rsx_web::node::render_to_string(|| {
+        rsx!(
+            {self::site()}
+            {Suspense(|| rsx!(<p>Loading</p>), || {
+                create_resource(pending::<()>());
+                rsx!(<p>Loaded</p>)
+            })}
+        )
+    });
+    assert_eq!(html, "<h1>Home</h1><p>Loading</p>");
+
+    let site = StaticSite::new(|| {
+        let resource = create_resource(pending::<()>());
+        rsx!(<p>{resource.value().map(|value| format!("{value:?}"))}</p>)
+    });
+    assert!(matches!(
+        site.render_page("/"),
+        Err(SsgError::UnresolvedResources { .. })
+    ));
+
+    let site = StaticSite::new(|| {
+        Suspense(
+            || rsx!(<p>Loading</p>),
+            || {
+                let resource = create_resource(pending::<()>());
+                rsx!(<p>{resource.value().map(|value| format!("{value:?}"))}</p>)
+            },
+        )
+    })
+    .timeout(Duration::from_millis(10));
+    assert!(matches!(
+        site.render_page("/"),
+        Err(SsgError::UnresolvedResources { .. })
+    ));
+
+    let site = StaticSite::new(self::site).route("/posts/<id>");
+    assert!(matches!(site.paths(), Err(SsgError::Pattern(_))));
+}
+
+/// Returns a paragraph with `value`, loaded on another thread after `delay`.
+fn delayed(value: &'static str, delay: Duration) -> rsx::Element {
+    let (tx, rx) = mpsc::channel();
+    let mut tx = Some(tx);
+    let resource = create_resource(poll_fn(move |cx| {
+        if let Some(tx) = tx.take() {
+            let waker = cx.waker().clone();
+            thread::spawn(move || {
+                thread::sleep(delay);
+                tx.send(value.to_string()).unwrap();
+                waker.wake();
+            });
+        }
+        rx.try_recv().map_or(Poll::Pending, Poll::Ready)
+    }));
+    rsx!(<p>{resource.value().map(|value| value.clone().unwrap_or_default())}</p>)
+}
+
+#[test]
+fn wait_for_resources_loaded_on_another_thread() {
+    let site = StaticSite::new(|| {
+        rsx!(
+            <ul>
+                {Suspense(|| rsx!(<li>Loading</li>), || {
+                    rsx!(
+                        <li>
+                            {delayed("Outer", Duration::from_millis(30))}
+                            {Suspense(
+                                || rsx!(<p>Loading</p>),
+                                || delayed("Inner", Duration::from_millis(10)),
+                            )}
+                        </li>
+                    )
+                })}
+            </ul>
+        )
+    });
+    let html = site.render_page("/").unwrap();
+    // The resolved content is rendered in place of the fallbacks, even if the nested boundary
+    // resolves first.
+    assert!(html.contains("<div id=\"app\"><ul><li><p>Outer</p><p>Inner</p></li></ul></div>"));
+    assert!(!html.contains("<template"));
+    assert!(!html.contains("$rsx"));
+}
+
+#[test]
+fn render_page_after_a_panicking_resource() {
+    let site = StaticSite::new(|| {
+        Suspense(
+            || rsx!(<p>Loading</p>),
+            || {
+                create_resource(async { panic!("boom") });
+                rsx!(<p>Loaded</p>)
+            },
+        )
+    });
+    assert!(matches!(
+        site.render_page("/"),
+        Err(SsgError::Render { message, .. }) if message == "boom"
+    ));
+
+    // Resources of later pages are still polled.
+    let site = StaticSite::new(|| {
+        Suspense(
+            || rsx!(<p>Loading</p>),
+            || delayed("Loaded", Duration::from_millis(10)),
+        )
+    })
+    .timeout(Duration::from_secs(5));
+    let html = site.render_page("/").unwrap();
+    assert!(html.contains("<div id=\"app\"><p>Loaded</p></div>"));
+}
+
+#[test]
+fn read_vite_manifest() {
+    let json = r#"{
+        "_shared.js": { "file": "assets/shared-1.js", "css": ["assets/shared-2.css"] },
+        "index.html": {
+            "file": "assets/index-3.js",
+            "src": "index.html",
+            "isEntry": true,
+            "imports": ["_shared.js"],
+            "css": ["assets/index-4.css"]
+        }
+    }"#;
+    assert_eq!(
+        Manifest::from_vite(json, "index.html").unwrap(),
+        Manifest {
+            scripts: vec!["/assets/index-3.js".to_string()],
+            preloads: vec!["/assets/shared-1.js".to_string()],
+            styles: vec![
+                "/assets/index-4.css".to_string(),
+                "/assets/shared-2.css".to_string()
+            ],
+        }
+    );
+    assert!(matches!(
+        Manifest::from_vite(json, "main.ts"),
+        Err(SsgError::Manifest(_))
+    ));
+    assert!(Manifest::from_vite("{", "index.html").is_err());
+}
@@ -0,0 +1,169 @@
+use rsx::{Attributes, node_ref::create_node_ref, rsx};
+use rsx_reactive::signals::create_signal;
+use rsx_web::{node::render_to_string, portal::Portal, suspense::Suspense};
+
+#[test]
+fn render_element_to_string() {
+    let html = render_to_string(|| {
+        rsx!(
+            <div title="\"quoted\"" class="container">
+                <input disabled></input>
+                {"<b>"}
+            </div>
+        )
+    });
+    assert_eq!(
+        html,
+        r#"<div class="container" title="&quot;quoted&quot;"><input disabled>&lt;b&gt;</div>"#
+    );
+}
+
+#[test]
+fn raw_text_is_not_escaped() {
+    let html = render_to_string(|| {
+        let (style, script) = ("a > b { color: red; }", "if (1 < 2 && true) {}");
+        rsx!(
+            <head>
+                <style>{style}</style>
+                <script>{script}</script>
+            </head>
+        )
+    });
+    assert_eq!(
+        html,
+        "<head><style>a > b { color: red; }</style><script>if (1 < 2 && true) {}</script></head>"
+    );
+}
+
+#[test]
+fn portals_are_not_rendered_to_string() {
+    let html = render_to_string(|| {
+        rsx!(
+            <main>
+                {Portal("#modals", || rsx!(<div class="modal">Hello</div>))}
+            </main>
+        )
+    });
+    assert_eq!(html, "<main></main>");
+}
+
+#[test]
+fn render_control_flow_to_string() {
+    let html = render_to_string(|| {
+        let count = create_signal(2);
+        rsx!(
+            <ul>
+                {move || if count.get() > 1 { <li>many</li> } else if count.get() == 1 { <li>one</li> }}
+                {move || for i in 0..count.get() { <li>{i}</li> }}
+                {match count.get() {
+                    0 => <li>none</li>,
+                    _ => { <li>some</li> <li>more</li> }
+                }}
+            </ul>
+        )
+    });
+    assert_eq!(
+        html,
+        "<ul><li>many</li><li>0</li><li>1</li><li>some</li> <li>more</li></ul>"
+    );
+}
+
+#[test]
+fn class_and_style_directives() {
+    let html = render_to_string(|| {
+        let active = create_signal(true);
+        let color = create_signal("red");
+        rsx!(
+            <div
+                class="item hidden"
+                class:active={active}
+                class:hidden={false}
+                class:selected
+                style="margin: 0;"
+                style:color={color}
+            ></div>
+        )
+    });
+    assert_eq!(
+        html,
+        r#"<div class="item active selected" style="margin: 0; color: red"></div>"#
+    );
+}
+
+#[test]
+fn spread_attributes() {
+    fn text_input(attrs: Attributes) -> rsx::Element {
+        rsx!(<input class="input" {..attrs} type="text" disabled={false}/>)
+    }
+
+    let html = render_to_string(|| {
+        let required = create_signal(true);
+        text_input(
+            Attributes::new()
+                .attr("class", "large")
+                .attr("type", "number")
+                .attr("disabled", true)
+                .attr("required", required)
+                .attr("maxlength", 8),
+        )
+    });
+    assert_eq!(
+        html,
+        r#"<input class="input large" maxlength="8" required type="text">"#
+    );
+}
+
+#[test]
+fn spread_a_variable_named_element() {
+    let html = render_to_string(|| {
+        let element = Attributes::new().attr("id", "user");
+        rsx!(<p {..element}>Spread</p>)
+    });
+    assert_eq!(html, r#"<p id="user">Spread</p>"#);
+}
+
+#[test]
+fn render_svg_to_string() {
+    let html = render_to_string(|| {
+        rsx!(
+            <svg viewBox="0 0 10 10" aria-hidden>
+                <use xlink:href="#icon" style:stroke-width="2"/>
+            </svg>
+        )
+    });
+    assert_eq!(
+        html,
+        r##"<svg aria-hidden viewBox="0 0 10 10"><use style="stroke-width: 2" xlink:href="#icon"></use></svg>"##
+    );
+}
+
+#[test]
+fn inner_html_is_rendered_verbatim() {
+    let html = render_to_string(|| {
+        let markdown = create_signal("<p>Hello <em>world</em></p>".to_string());
+        rsx!(<article dangerously_set_inner_html={markdown}></article>)
+    });
+    assert_eq!(html, "<article><p>Hello <em>world</em></p></article>");
+}
+
+#[test]
+fn node_refs_are_not_filled_on_the_server() {
+    let html = render_to_string(|| {
+        let input = create_node_ref();
+        let element = rsx!(<input ref={input}/>);
+        assert!(input.get::<web_sys::Element>().is_none());
+        element
+    });
+    assert_eq!(html, "<input>");
+}
+
+#[test]
+fn suspense_children_render_more_than_once() {
+    let html = render_to_string(|| {
+        let element = Suspense(|| rsx!(<p>Loading</p>), || rsx!(<p>Ready</p>));
+        // Displaying the element calls the dynamic element each time.
+        assert_eq!(element.to_string(), "<p>Ready</p>");
+        element
+    });
+    assert_eq!(html, "<p>Ready</p>");
+}
@@ -0,0 +1,185 @@
+use std::{
+    cell::RefCell,
+    future::{Future, poll_fn},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, mpsc},
+    task::{Context, Poll, Wake, Waker},
+    thread,
+    time::Duration,
+};
+
+use futures_core::Stream;
+use rsx::rsx;
+use rsx_reactive::futures::create_resource;
+use rsx_web::{
+    node::{RenderStream, render_to_stream},
+    suspense::Suspense,
+};
+
+/// A future completed by hand with [`Sender::send`].
+#[derive(Clone)]
+struct Receiver(Rc<RefCell<(Option<String>, Option<Waker>)>>);
+
+struct Sender(Rc<RefCell<(Option<String>, Option<Waker>)>>);
+
+fn channel() -> (Sender, Receiver) {
+    let shared = Rc::new(RefCell::new((None, None)));
+    (Sender(shared.clone()), Receiver(shared))
+}
+
+impl Sender {
+    fn send(self, value: &str) {
+        let waker = {
+            let mut shared = self.0.borrow_mut();
+            shared.0 = Some(value.to_string());
+            shared.1.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Receiver {
+    type Output = String;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<String> {
+        let mut shared = self.0.borrow_mut();
+        match shared.0.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                shared.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn poll(stream: &mut RenderStream) -> Poll<Option<String>> {
+    Pin::new(stream).poll_next(&mut Context::from_waker(Waker::noop()))
+}
+
+fn loaded(receiver: Receiver) -> rsx::Element {
+    let value = create_resource(receiver).value();
+    rsx!(<p>{value.map(|value| value.clone().unwrap_or_default())}</p>)
+}
+
+/// Children of a boundary showing the value of `receiver`.
+fn load(receiver: &Receiver) -> impl Fn() -> rsx::Element + 'static {
+    let receiver = receiver.clone();
+    move || loaded(receiver.clone())
+}
+
+#[test]
+fn stream_suspense_boundaries_out_of_order() {
+    let (first, first_rx) = channel();
+    let (second, second_rx) = channel();
+    let mut stream = render_to_stream(move || {
+        rsx!(
+            <main>
+                {Suspense(|| rsx!(<p>Loading 1</p>), load(&first_rx))}
+                {Suspense(|| rsx!(<p>Loading 2</p>), load(&second_rx))}
+                {Suspense(|| rsx!(<p>Loading 3</p>), || rsx!(<p>Ready</p>))}
+            </main>
+        )
+    });
+
+    assert_eq!(
+        poll(&mut stream),
+        Poll::Ready(Some(
+            concat!(
+                "<main>",
+                r#"<template id="rsx-suspense-0"></template><p>Loading 1</p>"#,
+                r#"<template id="rsx-suspense-end-0"></template>"#,
+                r#"<template id="rsx-suspense-1"></template><p>Loading 2</p>"#,
+                r#"<template id="rsx-suspense-end-1"></template>"#,
+                "<p>Ready</p>",
+                "</main>"
+            )
+            .to_string()
+        ))
+    );
+    assert_eq!(poll(&mut stream), Poll::Pending);
+
+    second.send("Second");
+    let Poll::Ready(Some(chunk)) = poll(&mut stream) else {
+        panic!("expected a chunk");
+    };
+    assert!(chunk.starts_with("<script>function $rsx(i)"));
+    assert!(chunk.ends_with(
+        r#"<template id="rsx-resolved-1"><p>Second</p></template><script>$rsx(1)</script>"#
+    ));
+    assert_eq!(poll(&mut stream), Poll::Pending);
+
+    first.send("First");
+    assert_eq!(
+        poll(&mut stream),
+        Poll::Ready(Some(
+            r#"<template id="rsx-resolved-0"><p>First</p></template><script>$rsx(0)</script>"#
+                .to_string()
+        ))
+    );
+    assert_eq!(poll(&mut stream), Poll::Ready(None));
+}
+
+#[test]
+fn suspense_renders_fallback_to_string() {
+    let html = rsx_web::node::render_to_string(|| {
+        Suspense(|| rsx!(<p>Loading</p>), || loaded(Receiver(Rc::default())))
+    });
+    assert_eq!(html, "<p>Loading</p>");
+}
+
+/// Sends a message every time it is woken.
+struct Notify(mpsc::Sender<()>);
+
+impl Wake for Notify {
+    fn wake(self: Arc<Self>) {
+        let _ = self.0.send(());
+    }
+}
+
+#[test]
+fn stream_boundary_woken_from_another_thread() {
+    let mut stream = render_to_stream(move || {
+        Suspense(
+            || rsx!(<p>Loading</p>),
+            move || {
+                let mut spawned = false;
+                let value = create_resource(poll_fn(move |cx| {
+                    if spawned {
+                        return Poll::Ready("Loaded".to_string());
+                    }
+                    spawned = true;
+                    let waker = cx.waker().clone();
+                    thread::spawn(move || waker.wake());
+                    Poll::Pending
+                }))
+                .value();
+                rsx!(<p>{value.map(|value| value.clone().unwrap_or_default())}</p>)
+            },
+        )
+    });
+
+    let (notify_tx, notify_rx) = mpsc::channel();
+    let waker = Waker::from(Arc::new(Notify(notify_tx)));
+    let mut cx = Context::from_waker(&waker);
+    assert!(matches!(
+        Pin::new(&mut stream).poll_next(&mut cx),
+        Poll::Ready(Some(_))
+    ));
+    // The resource may already have been woken, in which case this polls it.
+    let mut chunk = Pin::new(&mut stream).poll_next(&mut cx);
+    if chunk.is_pending() {
+        notify_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        chunk = Pin::new(&mut stream).poll_next(&mut cx);
+    }
+    let Poll::Ready(Some(chunk)) = chunk else {
+        panic!("expected a chunk");
+    };
+    assert!(chunk.ends_with(
+        r#"<template id="rsx-resolved-0"><p>Loaded</p></template><script>$rsx(0)</script>"#
+    ));
+    assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use rsx::rsx;
+use rsx_reactive::signals::create_signal;
+use rsx_web::{
+    node::render_to_string,
+    transition::{Transition, TransitionGroup},
+};
+
+#[test]
+fn render_transitions_to_string() {
+    let html = render_to_string(|| {
+        let items = create_signal(vec!["a", "b"]);
+        let duration = Duration::from_millis(200);
+        rsx!(
+            <main>
+                {Transition("fade", duration, || true, || rsx!(<p>Shown</p>))}
+                {Transition("fade", duration, || false, || rsx!(<p>Hidden</p>))}
+                {TransitionGroup(
+                    "list",
+                    duration,
+                    "ul",
+                    move || items.get_clone(),
+                    |item| *item,
+                    |item| rsx!(<li>{item}</li>),
+                )}
+            </main>
+        )
+    });
+    assert_eq!(
+        html,
+        "<main><p>Shown</p><ul><li>a</li><li>b</li></ul></main>"
+    );
+}